@@ -11,6 +11,7 @@
 
 extern crate term_ctrl;
 
+use term_ctrl::{seq, c256_ul, rgb_ul};
 use term_ctrl::predefined::*;
 use term_ctrl::predefined::colours::{fg, bg, RESET_BG};
 
@@ -89,6 +90,13 @@ fn main() {
     println!("  Double-overline:  {}{}{}", misc::ideogram::DBL_OVERLINE,   SAMPLE_TEXT, RESET);
     println!("  Stress-marking:   {}{}{}", misc::ideogram::STRESS_MARKING, SAMPLE_TEXT, RESET);
 
+    println!("Underline styles:");
+    println!("  Curly:            {}{}{}", effects::CURLY_UNDERLINE,  SAMPLE_TEXT, RESET);
+    println!("  Dotted:           {}{}{}", effects::DOTTED_UNDERLINE, SAMPLE_TEXT, RESET);
+    println!("  Dashed:           {}{}{}", effects::DASHED_UNDERLINE, SAMPLE_TEXT, RESET);
+    println!("  Curly (red):      {}{}{}", seq!("4:3", c256_ul!(9)),            SAMPLE_TEXT, RESET);
+    println!("  Curly (RGB):      {}{}{}", seq!("4:3", rgb_ul!(180, 15, 70)),   SAMPLE_TEXT, RESET);
+
     println!("Combinations - foreground-color + bold:");
     println!("  Black:            {}{}{}", combinations::fg_bold::BLACK,   SAMPLE_TEXT, RESET);
     println!("  Red:              {}{}{}", combinations::fg_bold::RED,     SAMPLE_TEXT, RESET);
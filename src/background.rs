@@ -0,0 +1,252 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Light and dark backgrounds
+//!
+//! A colour that reads well on a dark background may be all but invisible on a light one, and vice
+//! versa; yellow text on white being a classic example. This mod helps with detecting which kind of
+//! background the terminal has, as a [`Background`], and with choosing colours accordingly, with
+//! the [`Adaptive`] type.
+//!
+//! Once detected, the background can be made the [current](Background::set_current) one, against
+//! which adaptive colours held in a [`Colour`] (and thus a [`Style`](crate::style::Style)) are
+//! resolved as they are written out.
+//!
+//! ```rust
+//! use term_ctrl::background::{Adaptive, Background};
+//! use term_ctrl::colour::{BasicColour, Colour};
+//! use term_ctrl::style::Style;
+//!
+//! const WARNING: Adaptive = Adaptive {
+//!     light: Colour::Indexed(130),
+//!     dark: Colour::Basic(BasicColour::Yellow),
+//! };
+//!
+//! let style = Style::new().fg(&WARNING).bold();
+//!
+//! // Typically from `Background::detect()`
+//! let reply = "\u{1B}]11;rgb:ffff/ffff/ffff\u{1B}\\";
+//! Background::from_reply(reply).set_current();
+//! assert_eq!("\u{1B}[1;38;5;130m", format!("{}", style));
+//!
+//! // Or for a given background
+//! assert_eq!("33", format!("{}", WARNING.fg(Background::Dark)));
+//! ```
+//!
+//! The most reliable means of detection is asking the terminal for its background colour, via the
+//! [`palette`](mod@crate::palette) mod, as [`Background::detect()`] does. Where that is not
+//! possible, the `COLORFGBG` environment variable set by some terminals can be used instead.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::colour::{Codes, Colour};
+use crate::palette::{self, Target};
+
+/// The current background, as per `Background::to_u8()`
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Kind of terminal background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Background {
+    /// Light (e.g. black text on white)
+    Light,
+    /// Dark (e.g. white text on black)
+    Dark,
+    /// Unknown (it could not be detected)
+    Unknown,
+}
+
+impl Default for Background {
+    #[inline]
+    fn default() -> Self {
+        Background::Unknown
+    }
+}
+
+impl Background {
+    /// Gets the current background
+    ///
+    /// This is what adaptive colours held in a [`Colour`] are resolved against as they are written
+    /// out. It is `Unknown` unless set with [`set_current()`](Self::set_current).
+    #[inline]
+    pub fn current() -> Self {
+        Self::from_u8(CURRENT.load(Ordering::Relaxed))
+    }
+
+    /// Makes this the current background, for the whole process
+    #[inline]
+    pub fn set_current(self) {
+        CURRENT.store(self.to_u8(), Ordering::Relaxed);
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Background::Unknown => 0,
+            Background::Light => 1,
+            Background::Dark => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Background::Light,
+            2 => Background::Dark,
+            _ => Background::Unknown,
+        }
+    }
+
+    /// Determines the kind of background from its colour
+    ///
+    /// This is based upon the perceived brightness of the colour.
+    pub fn from_rgb((r, g, b): (u8, u8, u8)) -> Self {
+        let brightness = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+        match brightness > 127 {
+            true => Background::Light,
+            false => Background::Dark,
+        }
+    }
+
+    /// Determines the kind of background from a reply to a background colour query
+    ///
+    /// Gives `Unknown` if the reply is not a valid reply to such a query (see
+    /// [`palette::parse_reply()`]).
+    pub fn from_reply(reply: &str) -> Self {
+        match palette::parse_reply(reply) {
+            Ok((Target::Background, rgb)) => Self::from_rgb(rgb),
+            _ => Background::Unknown,
+        }
+    }
+
+    /// Determines the kind of background from the value of a `COLORFGBG` environment variable
+    ///
+    /// The value takes a form such as `15;0`, with the final field giving the background as a
+    /// basic palette colour number. Going by the common defaults, colours 7 (white) and 9 to 15
+    /// (bright colours other than bright black) are taken to be light, the rest dark. Anything
+    /// else gives `Unknown`.
+    pub fn from_colorfgbg(value: &str) -> Self {
+        match value.rsplit(';').next().and_then(|bg| bg.parse::<u8>().ok()) {
+            Some(7) | Some(9..=15) => Background::Light,
+            Some(0..=6) | Some(8) => Background::Dark,
+            _ => Background::Unknown,
+        }
+    }
+
+    /// Determines the kind of background from the environment
+    ///
+    /// This uses the `COLORFGBG` variable, as per [`from_colorfgbg()`](Self::from_colorfgbg),
+    /// with `env` used to look it up, as with
+    /// [`support::colour_level()`](crate::support::colour_level).
+    pub fn from_env<F, V>(env: F) -> Self
+        where F: Fn(&str) -> Option<V>, V: AsRef<str>
+    {
+        env("COLORFGBG").map_or(Background::Unknown, |v| Self::from_colorfgbg(v.as_ref()))
+    }
+
+    /// Determines the kind of background from the process environment
+    ///
+    /// This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn from_env_vars() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Asks a terminal for its background colour
+    ///
+    /// This sends a background colour [query](palette::Query) with
+    /// [`support::round_trip()`](crate::support::round_trip), giving the kind of background going
+    /// by the reply, or `Unknown` if there is none, along with any other input read meanwhile.
+    ///
+    /// This requires the `std` feature.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn from_terminal(fd: std::os::unix::io::RawFd, timeout: std::time::Duration)
+        -> std::io::Result<(Self, std::vec::Vec<u8>)>
+    {
+        let query = std::format!("{}", palette::Query::new(Target::Background));
+        let (reply, other) = crate::support::round_trip(fd, &query, timeout)?;
+        Ok((Self::from_reply_bytes(reply), other))
+    }
+
+    /// Detects the kind of background of the controlling terminal
+    ///
+    /// This asks the terminal, as [`from_terminal()`](Self::from_terminal) does but via
+    /// `/dev/tty`, falling back to the environment, as per
+    /// [`from_env_vars()`](Self::from_env_vars), where that fails or the terminal does not support
+    /// the query. Along with the result is given any other input read meanwhile.
+    ///
+    /// This requires the `std` feature.
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use term_ctrl::background::Background;
+    ///
+    /// let (background, _other_input) = Background::detect(Duration::from_millis(100));
+    /// background.set_current();
+    /// ```
+    #[cfg(all(unix, feature = "std"))]
+    pub fn detect(timeout: std::time::Duration) -> (Self, std::vec::Vec<u8>) {
+        let query = std::format!("{}", palette::Query::new(Target::Background));
+        let (background, other) = match crate::support::round_trip_tty(&query, timeout) {
+            Ok((reply, other)) => (Self::from_reply_bytes(reply), other),
+            Err(_) => (Background::Unknown, std::vec::Vec::new()),
+        };
+        match background {
+            Background::Unknown => (Self::from_env_vars(), other),
+            background => (background, other),
+        }
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    fn from_reply_bytes(reply: Option<std::vec::Vec<u8>>) -> Self {
+        reply.and_then(|r| std::string::String::from_utf8(r).ok())
+            .map_or(Background::Unknown, |r| Self::from_reply(&r))
+    }
+}
+
+/// A colour with variants for light and dark backgrounds
+///
+/// Where the background is unknown, the dark variant is used, dark backgrounds being the more
+/// common.
+///
+/// To have the colour chosen as it is written out, going by the
+/// [current background](Background::current), use it via [`Colour::Adaptive`], which a
+/// `&'static Adaptive` converts into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Adaptive {
+    /// Colour for use on a light background
+    pub light: Colour,
+    /// Colour for use on a dark background
+    pub dark: Colour,
+}
+
+impl Adaptive {
+    /// Creates a new adaptive colour
+    #[inline]
+    pub fn new(light: Colour, dark: Colour) -> Self {
+        Self { light, dark }
+    }
+
+    /// Gets the colour to use on a background
+    #[inline]
+    pub fn resolve(self, background: Background) -> Colour {
+        match background {
+            Background::Light => self.light,
+            Background::Dark | Background::Unknown => self.dark,
+        }
+    }
+
+    /// Gives the code set for using this colour as the text colour on a background
+    #[inline]
+    pub fn fg(self, background: Background) -> Codes {
+        self.resolve(background).fg()
+    }
+
+    /// Gives the code set for using this colour as the background-highlight colour on a background
+    #[inline]
+    pub fn bg(self, background: Background) -> Codes {
+        self.resolve(background).bg()
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Clipboard access
+//!
+//! The OSC 52 sequence has the terminal place data on the clipboard, or report what is on it. Since
+//! it is the terminal that does this, it works even from a program running on a remote host over
+//! SSH. The data is base64 encoded within the sequence.
+//!
+//! ```rust
+//! use term_ctrl::clipboard::Set;
+//!
+//! let set = Set::new(b"cargo build").unwrap();
+//! assert_eq!("\u{1B}]52;c;Y2FyZ28gYnVpbGQ=\u{1B}\\", format!("{}", set));
+//! ```
+//!
+//! Many terminals disable this feature, or just the querying part of it, by default, and most
+//! limit the amount of data accepted, hence a [limit](DEFAULT_LIMIT) being applied on creating a
+//! sequence. A query is answered, if at all, with a sequence of the same form carrying the data,
+//! which [`parse_reply()`] can decode (requiring the `alloc` feature).
+
+use core::fmt::{self, Write};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::osc::{Terminator, OSC_PREFIX};
+
+/// The default limit on the length of data, once encoded, that a [`Set`] may hold
+///
+/// This is a conservative figure based on the limits of common terminals and multiplexers.
+pub const DEFAULT_LIMIT: usize = 74_994;
+
+/// Base64 alphabet
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Selection to use
+///
+/// Terminals on platforms without a primary selection, or which do not distinguish them, will
+/// typically treat all of these as the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Selection {
+    /// The clipboard (`c`)
+    Clipboard,
+    /// The primary selection (`p`), as pasted with the middle mouse button under X11
+    Primary,
+    /// The selection as configured in the terminal (`s`)
+    Select,
+}
+
+impl Default for Selection {
+    #[inline]
+    fn default() -> Self {
+        Selection::Clipboard
+    }
+}
+
+impl Selection {
+    /// Gets the char representing the selection in the sequence
+    #[inline]
+    pub fn as_char(self) -> char {
+        match self {
+            Selection::Clipboard => 'c',
+            Selection::Primary => 'p',
+            Selection::Select => 's',
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'c' => Some(Selection::Clipboard),
+            'p' => Some(Selection::Primary),
+            's' => Some(Selection::Select),
+            _ => None,
+        }
+    }
+}
+
+/// Error in creating or parsing a clipboard sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// The data is too large to send (once encoded)
+    TooLarge,
+    /// Not a clipboard reply sequence
+    NotReply,
+    /// The data in a reply is not valid base64
+    InvalidData,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Error::TooLarge => "data too large for clipboard sequence",
+            Error::NotReply => "not a clipboard reply sequence",
+            Error::InvalidData => "invalid base64 data in clipboard reply",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Sequence placing data on the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Set<'a> {
+    data: &'a [u8],
+    selection: Selection,
+    terminator: Terminator,
+}
+
+impl<'a> Set<'a> {
+    /// Creates a sequence placing `data` on the clipboard
+    ///
+    /// This fails if the data is larger than [`DEFAULT_LIMIT`] once encoded.
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        Self::with_limit(data, DEFAULT_LIMIT)
+    }
+
+    /// Creates a sequence placing `data` on the clipboard, with a specific limit
+    ///
+    /// This fails if the data is larger than `limit` once encoded.
+    pub fn with_limit(data: &'a [u8], limit: usize) -> Result<Self, Error> {
+        if encoded_len(data.len()) > limit {
+            return Err(Error::TooLarge);
+        }
+        Ok(Self { data, selection: Selection::default(), terminator: Terminator::default() })
+    }
+
+    /// Sets the selection to use (defaults to the clipboard)
+    #[inline]
+    pub fn selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl<'a> fmt::Display for Set<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}52;{};", OSC_PREFIX, self.selection.as_char())?;
+        write_base64(f, self.data)?;
+        f.write_str(self.terminator.as_str())
+    }
+}
+
+/// Sequence querying the clipboard content
+///
+/// If the terminal allows it, it replies with a sequence that [`parse_reply()`] can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Query {
+    pub selection: Selection,
+    pub terminator: Terminator,
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}52;{};?{}", OSC_PREFIX, self.selection.as_char(), self.terminator)
+    }
+}
+
+/// Parses a reply to a clipboard query, returning the selection and its decoded content
+///
+/// The reply is expected to be the complete sequence, with either terminator.
+#[cfg(feature = "alloc")]
+pub fn parse_reply(reply: &str) -> Result<(Selection, Vec<u8>), Error> {
+    let body = crate::osc::reply_body(reply, OSC_PREFIX)
+        .filter(|s| s.starts_with("52;"))
+        .ok_or(Error::NotReply)?;
+    let mut parts = body[3..].splitn(2, ';');
+    let (selection, data) = match (parts.next(), parts.next()) {
+        (Some(selection), Some(data)) => (selection, data),
+        _ => return Err(Error::NotReply),
+    };
+    // The terminal may report more than one selection letter; take the first we understand
+    let selection = selection.chars().find_map(Selection::from_char).ok_or(Error::NotReply)?;
+    let data = decode_base64(data).ok_or(Error::InvalidData)?;
+    Ok((selection, data))
+}
+
+#[inline]
+fn encoded_len(len: usize) -> usize {
+    (len + 2) / 3 * 4
+}
+
+fn write_base64(f: &mut fmt::Formatter, data: &[u8]) -> fmt::Result {
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => f.write_char(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char)?,
+                false => f.write_char('=')?,
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "alloc")]
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let data = data.as_bytes();
+    if data.len() % 4 != 0 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3);
+    for (i, chunk) in data.chunks(4).enumerate() {
+        let last = i == data.len() / 4 - 1;
+        let padding = match (chunk[2], chunk[3]) {
+            (b'=', b'=') if last => 2,
+            (_, b'=') if last => 1,
+            _ => 0,
+        };
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            n = n << 6 | ALPHABET.iter().position(|&a| a == c)? as u32;
+        }
+        n <<= 6 * padding as u32;
+        out.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8][..3 - padding]);
+    }
+    Some(out)
+}
@@ -0,0 +1,301 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Colour selection
+//!
+//! Typed equivalents of the colour code constants, for use with [`Style`](crate::style::Style), or
+//! directly via the [`fg()`](Colour::fg), [`bg()`](Colour::bg) and [`ul()`](Colour::ul) methods
+//! which give the code set for a colour, much like the `c256_fg` and `rgb_fg` macros do for
+//! literals.
+//!
+//! ```rust
+//! use term_ctrl::colour::{Colour, BasicColour};
+//! assert_eq!("31", format!("{}", Colour::Basic(BasicColour::Red).fg()));
+//! assert_eq!("101", format!("{}", Colour::Bright(BasicColour::Red).bg()));
+//! assert_eq!("38;5;238", format!("{}", Colour::Indexed(238).fg()));
+//! assert_eq!("48;2;180;15;70", format!("{}", Colour::Rgb(180, 15, 70).bg()));
+//! assert_eq!("58;5;9", format!("{}", Colour::Bright(BasicColour::Red).ul()));
+//! ```
+//!
+//! # Colour depth
+//!
+//! Not every terminal supports the full range of colours. Colours can be downgraded to the nearest
+//! equivalent for a given [`ColourLevel`] with [`Colour::downgrade()`] (or a whole style with
+//! [`Style::downgrade()`](crate::style::Style::downgrade)).
+//!
+//! ```rust
+//! use term_ctrl::colour::{Colour, BasicColour, ColourLevel};
+//! let colour = Colour::Rgb(180, 15, 70);
+//! assert_eq!(Some(colour), colour.downgrade(ColourLevel::TrueColour));
+//! assert_eq!(Some(Colour::Indexed(125)), colour.downgrade(ColourLevel::Ansi256));
+//! assert_eq!(Some(Colour::Basic(BasicColour::Red)), colour.downgrade(ColourLevel::Ansi16));
+//! assert_eq!(None, colour.downgrade(ColourLevel::None));
+//! ```
+
+use core::fmt;
+use crate::background::{Adaptive, Background};
+
+// Aliases for the non-British-English speakers
+pub type Color = Colour;
+pub type BasicColor = BasicColour;
+pub type ColorLevel = ColourLevel;
+
+/// The eight colours of the basic palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BasicColour {
+    Black = 0,
+    Red = 1,
+    Green = 2,
+    Yellow = 3,
+    Blue = 4,
+    Magenta = 5,
+    Cyan = 6,
+    White = 7,
+}
+
+/// A text (foreground), background-highlight or underline colour selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    /// One of the basic palette colours (codes `30`-`37` and `40`-`47`)
+    Basic(BasicColour),
+    /// The “bright” variant of one of the basic palette colours (codes `90`-`97` and `100`-`107`)
+    Bright(BasicColour),
+    /// A 256-colour palette selection (codes `38;5;n` and `48;5;n`)
+    Indexed(u8),
+    /// An RGB colour (codes `38;2;r;g;b` and `48;2;r;g;b`)
+    Rgb(u8, u8, u8),
+    /// The terminal’s default colour (codes `39` and `49`)
+    Default,
+    /// A colour with variants for light and dark backgrounds, chosen as it is written out, going by
+    /// the [current background](Background::current)
+    ///
+    /// The variants are not themselves meant to be adaptive; any that are are taken as the default
+    /// colour when written out or downgraded. (Note that one referring back to itself cannot be
+    /// compared, hashed or debug-formatted, as these go through the variants.)
+    Adaptive(&'static Adaptive),
+}
+
+impl From<BasicColour> for Colour {
+    #[inline]
+    fn from(c: BasicColour) -> Self {
+        Colour::Basic(c)
+    }
+}
+
+impl From<&'static Adaptive> for Colour {
+    #[inline]
+    fn from(c: &'static Adaptive) -> Self {
+        Colour::Adaptive(c)
+    }
+}
+
+/// Level of colour support
+///
+/// Levels are ordered, from no support up to full RGB support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColourLevel {
+    /// No colour support; no sequences at all should be used
+    None,
+    /// Just the basic and bright palette colours
+    Ansi16,
+    /// The 256-colour palette
+    Ansi256,
+    /// RGB colour
+    TrueColour,
+}
+
+/// Default RGB values of the basic (`0`-`7`) and bright (`8`-`15`) palette colours (as per xterm)
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// Component levels of the 6x6x6 colour cube of the 256-colour palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The basic palette colours, in order of their numbers
+pub(crate) const BASIC: [BasicColour; 8] = [
+    BasicColour::Black, BasicColour::Red, BasicColour::Green, BasicColour::Yellow,
+    BasicColour::Blue, BasicColour::Magenta, BasicColour::Cyan, BasicColour::White,
+];
+
+impl Colour {
+    /// Downgrades the colour to the nearest equivalent supported at the given level
+    ///
+    /// Gives `None` for [`ColourLevel::None`]. Colours already supported at the given level are
+    /// returned unchanged. An adaptive colour is returned unchanged where both of its variants are
+    /// supported, and otherwise is resolved against the current background and then downgraded.
+    pub fn downgrade(self, level: ColourLevel) -> Option<Colour> {
+        let colour = match (level, self) {
+            (ColourLevel::None, _) => return None,
+            (ColourLevel::Ansi16, Colour::Indexed(n)) if n < 16 => ansi16(n),
+            (ColourLevel::Ansi16, Colour::Indexed(n)) => {
+                let (r, g, b) = indexed_to_rgb(n);
+                rgb_to_16(r, g, b)
+            },
+            (ColourLevel::Ansi16, Colour::Rgb(r, g, b)) => rgb_to_16(r, g, b),
+            (ColourLevel::Ansi256, Colour::Rgb(r, g, b)) => Colour::Indexed(rgb_to_256(r, g, b)),
+            (_, Colour::Adaptive(adaptive)) => {
+                let supported = |c: Colour| variant(c).downgrade(level) == Some(variant(c));
+                match supported(adaptive.light) && supported(adaptive.dark) {
+                    true => self,
+                    false => return resolve(adaptive).downgrade(level),
+                }
+            },
+            (_, colour) => colour,
+        };
+        Some(colour)
+    }
+
+    /// Gives the code set for using this colour as the text (foreground) colour
+    #[inline]
+    pub fn fg(self) -> Codes {
+        Codes { colour: self, layer: Layer::Fg }
+    }
+
+    /// Gives the code set for using this colour as the background-highlight colour
+    #[inline]
+    pub fn bg(self) -> Codes {
+        Codes { colour: self, layer: Layer::Bg }
+    }
+
+    /// Gives the code set for using this colour as the underline colour
+    ///
+    /// There are no basic palette codes for underline colour, so basic and bright colours are
+    /// given as their 256-colour palette equivalents (`58;5;n`), and the default as code `59`. See
+    /// [underline styles and colour](crate#underline-styles-and-colour).
+    #[inline]
+    pub fn ul(self) -> Codes {
+        Codes { colour: self, layer: Layer::Ul }
+    }
+}
+
+/// What a colour is being applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Layer {
+    Fg,
+    Bg,
+    Ul,
+}
+
+/// The code set for a colour selection
+///
+/// Obtained from [`Colour::fg()`], [`Colour::bg()`] or [`Colour::ul()`], this writes out the code
+/// set when formatted (without the prefix and postfix of a complete sequence).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Codes {
+    colour: Colour,
+    layer: Layer,
+}
+
+impl fmt::Display for Codes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // There are no basic palette codes for underline colour, so 256-colour equivalents are used
+        let colour = match (self.layer, self.colour) {
+            (Layer::Ul, Colour::Basic(c)) => Colour::Indexed(c as u8),
+            (Layer::Ul, Colour::Bright(c)) => Colour::Indexed(8 + c as u8),
+            (_, colour) => colour,
+        };
+        // Base codes for the basic palette, bright palette, extended and default respectively
+        let (basic, bright, extended, default) = match self.layer {
+            Layer::Fg => (30, 90, 38, 39),
+            Layer::Bg => (40, 100, 48, 49),
+            Layer::Ul => (0, 0, 58, 59),
+        };
+        match colour {
+            Colour::Basic(c) => write!(f, "{}", basic + c as u8),
+            Colour::Bright(c) => write!(f, "{}", bright + c as u8),
+            Colour::Indexed(n) => write!(f, "{};5;{}", extended, n),
+            Colour::Rgb(r, g, b) => write!(f, "{};2;{};{};{}", extended, r, g, b),
+            Colour::Default => write!(f, "{}", default),
+            Colour::Adaptive(adaptive) => {
+                fmt::Display::fmt(&Codes { colour: resolve(adaptive), layer: self.layer }, f)
+            },
+        }
+    }
+}
+
+/// Resolves an adaptive colour against the current background
+#[inline]
+fn resolve(adaptive: &Adaptive) -> Colour {
+    variant(adaptive.resolve(Background::current()))
+}
+
+/// Gets the colour used for a variant of an adaptive colour, which cannot itself be adaptive
+#[inline]
+fn variant(colour: Colour) -> Colour {
+    match colour {
+        Colour::Adaptive(_) => Colour::Default,
+        colour => colour,
+    }
+}
+
+/// Gets the (default) RGB value of a 256-colour palette colour
+///
+/// Note that the first sixteen are the basic and bright palette colours, which users can and do
+/// often customise, so the values given for these are just the xterm defaults.
+pub fn indexed_to_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16_RGB[n as usize],
+        16..=231 => {
+            let n = n - 16;
+            let level = |i: u8| CUBE_LEVELS[i as usize];
+            (level(n / 36), level((n / 6) % 6), level(n % 6))
+        },
+        _ => {
+            let grey = 8 + (n - 232) * 10;
+            (grey, grey, grey)
+        },
+    }
+}
+
+/// Gets the nearest 256-colour palette colour to an RGB colour
+///
+/// Only the colour cube and greyscale ramp parts of the palette are considered, not the first
+/// sixteen colours, since their exact values vary between terminals.
+pub fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let rgb = (r, g, b);
+    // Nearest colour cube entry, taking the nearest level for each component
+    let cube_index = |c: u8| {
+        (0..6).min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(c)).abs()).unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * cube_index(r) + 6 * cube_index(g) + cube_index(b);
+    // Nearest greyscale ramp entry, going by the average of the components
+    let avg = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let grey = 232 + ((avg.saturating_sub(3) / 10).min(23) as u8);
+    match distance(indexed_to_rgb(grey), rgb) < distance(indexed_to_rgb(cube), rgb) {
+        true => grey,
+        false => cube,
+    }
+}
+
+/// Gets the nearest basic or bright palette colour to an RGB colour
+///
+/// This goes by the xterm default values of those colours.
+pub fn rgb_to_16(r: u8, g: u8, b: u8) -> Colour {
+    let nearest = (0..16u8).min_by_key(|&i| distance(ANSI16_RGB[i as usize], (r, g, b)))
+        .unwrap_or(0);
+    ansi16(nearest)
+}
+
+/// Gets the basic or bright palette colour for a 256-colour palette index under sixteen
+#[inline]
+fn ansi16(n: u8) -> Colour {
+    match n {
+        0..=7 => Colour::Basic(BASIC[n as usize]),
+        _ => Colour::Bright(BASIC[(n & 7) as usize]),
+    }
+}
+
+/// Squared distance between two RGB colours
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
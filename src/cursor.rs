@@ -0,0 +1,180 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Cursor movement
+//!
+//! Types for cursor movement and positioning sequences taking runtime values, each writing out the
+//! sequence when formatted. Where the values are known at compile time, the equivalent macros (such
+//! as [`cursor_up`](macro@crate::cursor_up)) can be used instead, and sequences for saving and
+//! restoring the cursor position are available in the `predefined::cursor` mod.
+//!
+//! ```rust
+//! use term_ctrl::cursor::{Up, Column};
+//! use term_ctrl::predefined::cursor::{SAVE, RESTORE};
+//!
+//! let lines = 3;
+//! // Update the start of a status line a few lines up, then go back to where we were
+//! print!("{}{}{}status{}", SAVE, Up(lines), Column(1), RESTORE);
+//! assert_eq!("\u{1B}[3A\u{1B}[1G", format!("{}{}", Up(lines), Column(1)));
+//! ```
+//!
+//! Rows and columns are one-based, with row one being the top and column one the left edge. Note
+//! that terminals treat a count of zero as one for the relative movements.
+//!
+//! The terminal can also be asked where the cursor is, with the `predefined::cursor` mod
+//! `REPORT_POSITION` query, replying with a position report that the functions here can parse.
+//! Note that the reply can be mistaken for a key press; a report of a position in the first row,
+//! such as `CSI 1 ; 2 R`, is identical to the legacy encoding of Shift+F3.
+
+use core::fmt;
+use core::ops::Range;
+
+/// Cursor up (CUU): moves the cursor up a number of lines, stopping at the top
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Up(pub u16);
+
+/// Cursor down (CUD): moves the cursor down a number of lines, stopping at the bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Down(pub u16);
+
+/// Cursor forward (CUF): moves the cursor right a number of columns, stopping at the right edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Forward(pub u16);
+
+/// Cursor back (CUB): moves the cursor left a number of columns, stopping at the left edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Back(pub u16);
+
+/// Cursor next line (CNL): moves the cursor to the start of the line a number of lines down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NextLine(pub u16);
+
+/// Cursor previous line (CPL): moves the cursor to the start of the line a number of lines up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrevLine(pub u16);
+
+/// Cursor horizontal absolute (CHA): moves the cursor to a column of the current line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Column(pub u16);
+
+/// Cursor position (CUP): moves the cursor to a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub row: u16,
+    pub col: u16,
+}
+
+/// Horizontal and vertical position (HVP): moves the cursor to a position
+///
+/// This is equivalent to [`Position`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HvPosition {
+    pub row: u16,
+    pub col: u16,
+}
+
+macro_rules! impl_display {
+    ($type:ident, $final:expr) => {
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "\u{1B}[{}{}", self.0, $final)
+            }
+        }
+    };
+    ($type:ident { row, col }, $final:expr) => {
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "\u{1B}[{};{}{}", self.row, self.col, $final)
+            }
+        }
+    };
+}
+
+impl_display!(Up, 'A');
+impl_display!(Down, 'B');
+impl_display!(Forward, 'C');
+impl_display!(Back, 'D');
+impl_display!(NextLine, 'E');
+impl_display!(PrevLine, 'F');
+impl_display!(Column, 'G');
+impl_display!(Position { row, col }, 'H');
+impl_display!(HvPosition { row, col }, 'f');
+
+impl Position {
+    /// Creates a new position
+    #[inline]
+    pub fn new(row: u16, col: u16) -> Self {
+        Self { row, col }
+    }
+}
+
+impl HvPosition {
+    /// Creates a new position
+    #[inline]
+    pub fn new(row: u16, col: u16) -> Self {
+        Self { row, col }
+    }
+}
+
+/// Parses a cursor position report (`CSI row ; col R`)
+///
+/// The report is expected to be the complete sequence.
+///
+/// ```rust
+/// use term_ctrl::cursor::{parse_position_report, Position};
+///
+/// assert_eq!(Some(Position::new(12, 1)), parse_position_report(b"\x1B[12;1R"));
+/// ```
+pub fn parse_position_report(report: &[u8]) -> Option<Position> {
+    match find_position_report(report)? {
+        (position, range) if range == (0..report.len()) => Some(position),
+        _ => None,
+    }
+}
+
+/// Finds the first cursor position report within some input
+///
+/// This gives the position reported along with where the report lies in the input, such that it
+/// can be removed, leaving other input intact.
+///
+/// ```rust
+/// use term_ctrl::cursor::{find_position_report, Position};
+///
+/// let input = b"ab\x1B[3;40Rc";
+/// assert_eq!(Some((Position::new(3, 40), 2..9)), find_position_report(input));
+/// ```
+pub fn find_position_report(input: &[u8]) -> Option<(Position, Range<usize>)> {
+    let mut from = 0;
+    while let Some(pos) = input[from..].windows(2).position(|w| w == b"\x1B[") {
+        let start = from + pos;
+        if let Some((position, len)) = position_report(&input[start + 2..]) {
+            return Some((position, start..start + 2 + len));
+        }
+        from = start + 1;
+    }
+    None
+}
+
+/// Parses the parameters and final byte of a position report, giving the position and length
+fn position_report(input: &[u8]) -> Option<(Position, usize)> {
+    let number = |input: &[u8]| -> Option<(u16, usize)> {
+        let len = input.iter().take_while(|b| b.is_ascii_digit()).count();
+        let digits = core::str::from_utf8(&input[..len]).ok()?;
+        Some((digits.parse().ok()?, len))
+    };
+    let (row, row_len) = number(input)?;
+    if input.get(row_len) != Some(&b';') {
+        return None;
+    }
+    let (col, col_len) = number(&input[row_len + 1..])?;
+    let len = row_len + 1 + col_len;
+    match input.get(len) {
+        Some(b'R') => Some((Position { row, col }, len + 1)),
+        _ => None,
+    }
+}
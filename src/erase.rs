@@ -0,0 +1,71 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Erasing
+//!
+//! Types for the erase sequences, each writing out the sequence when formatted. The same
+//! sequences are also available in fixed form in the `predefined::erase` mod, and via the
+//! [`erase_chars`](macro@crate::erase_chars) macro.
+//!
+//! ```rust
+//! use term_ctrl::erase::{Line, Chars};
+//!
+//! // Clear the current line, ready to redraw a progress indicator
+//! print!("{}\r", Line::All);
+//! assert_eq!("\u{1B}[2K\u{1B}[5X", format!("{}{}", Line::All, Chars(5)));
+//! ```
+//!
+//! Note that erasing does not move the cursor.
+
+use core::fmt;
+
+/// Erase in display (ED)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Screen {
+    /// Erase from the cursor to the end of the screen
+    Below = 0,
+    /// Erase from the start of the screen to the cursor
+    Above = 1,
+    /// Erase the entire screen
+    All = 2,
+    /// Erase the scrollback buffer (saved lines)
+    Scrollback = 3,
+}
+
+/// Erase in line (EL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Line {
+    /// Erase from the cursor to the end of the line
+    Right = 0,
+    /// Erase from the start of the line to the cursor
+    Left = 1,
+    /// Erase the entire line
+    All = 2,
+}
+
+/// Erase character (ECH): erases a number of chars from the cursor onwards, within the line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chars(pub u16);
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[{}J", *self as u8)
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[{}K", *self as u8)
+    }
+}
+
+impl fmt::Display for Chars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[{}X", self.0)
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Terminal state restoration
+//!
+//! If a program exits, or panics, after having applied formatting or changed a mode (such as
+//! switching to the alternate screen buffer) without undoing it, the user’s terminal is left in
+//! that state. The [`Guard`] type here helps avoid that; changes made through it are recorded, and
+//! undone when it is dropped, including when dropped whilst unwinding from a panic.
+//!
+//! ```rust
+//! use std::io::Write;
+//! use term_ctrl::guard::Guard;
+//! use term_ctrl::modes::Mode;
+//! use term_ctrl::predefined::effects::BOLD;
+//!
+//! let mut out = Guard::new(Vec::new());
+//! out.set_mode(Mode::AltScreen).unwrap();
+//! write!(out, "{}Hello", BOLD).unwrap();
+//! let out = out.into_inner();
+//! assert_eq!(b"\x1B[?1049h\x1B[1mHello\x1B[0m\x1B[?1049l", &out[..]);
+//! ```
+//!
+//! Since output may be left unflushed or the stack may not be unwound when a panic occurs (where
+//! the `panic = "abort"` profile setting is in use), a panic hook that performs the same cleanup
+//! for guards of stdout and stderr can also be installed with [`install_panic_hook()`].
+//!
+//! This requires the `std` feature.
+
+use std::boxed::Box;
+use std::io::{self, Write};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+use crate::modes::{Mode, MODE_COUNT};
+use crate::predefined::RESET;
+
+/// Changes made through guards of stdout, for the panic hook
+static STDOUT: Record = Record::new();
+/// Changes made through guards of stderr, for the panic hook
+static STDERR: Record = Record::new();
+
+/// Record of the changes made through the guards of a stream not yet dropped
+///
+/// So that each guard’s changes can be added and removed independently, this holds the number of
+/// guards having made each change.
+struct Record {
+    /// Guards having set each mode (in the order of `Mode::ALL`)
+    modes_set: [AtomicUsize; MODE_COUNT],
+    /// Guards having reset each mode (in the order of `Mode::ALL`)
+    modes_reset: [AtomicUsize; MODE_COUNT],
+    /// Guards having applied formatting
+    formatted: AtomicUsize,
+}
+
+impl Record {
+    // (The arrays are written out in full since array repeat expressions need `Copy` values; a
+    // mismatch with `MODE_COUNT` fails to compile.)
+    const fn new() -> Self {
+        Self {
+            modes_set: [
+                AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0),
+                AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0),
+                AtomicUsize::new(0), AtomicUsize::new(0),
+            ],
+            modes_reset: [
+                AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0),
+                AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0),
+                AtomicUsize::new(0), AtomicUsize::new(0),
+            ],
+            formatted: AtomicUsize::new(0),
+        }
+    }
+
+    /// Adds (or removes) the changes made through a guard
+    fn update(&self, (set, reset, formatted): (u16, u16, bool), add: bool) {
+        let update = |count: &AtomicUsize| match add {
+            true => count.fetch_add(1, Ordering::SeqCst),
+            false => count.fetch_sub(1, Ordering::SeqCst),
+        };
+        for i in 0..MODE_COUNT {
+            if set & 1 << i != 0 {
+                update(&self.modes_set[i]);
+            }
+            if reset & 1 << i != 0 {
+                update(&self.modes_reset[i]);
+            }
+        }
+        if formatted {
+            update(&self.formatted);
+        }
+    }
+
+    /// Gets the combined changes made through all guards
+    fn get(&self) -> (u16, u16, bool) {
+        let (mut set, mut reset) = (0, 0);
+        for i in 0..MODE_COUNT {
+            if self.modes_set[i].load(Ordering::SeqCst) != 0 {
+                set |= 1 << i;
+            }
+            if self.modes_reset[i].load(Ordering::SeqCst) != 0 {
+                reset |= 1 << i;
+            }
+        }
+        (set, reset, self.formatted.load(Ordering::SeqCst) != 0)
+    }
+}
+
+/// Terminal stream wrapped by a guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    #[inline]
+    fn record(self) -> &'static Record {
+        match self {
+            Stream::Stdout => &STDOUT,
+            Stream::Stderr => &STDERR,
+        }
+    }
+}
+
+/// Writer that restores terminal state when dropped
+///
+/// Modes changed through [`set_mode()`](Self::set_mode) and [`reset_mode()`](Self::reset_mode)
+/// are changed back. If any escape sequence at all has been written through the
+/// guard, it is assumed to have applied formatting, and a reset is written.
+#[derive(Debug)]
+pub struct Guard<W: Write> {
+    // Only `None` after `into_inner()`
+    out: Option<W>,
+    modes_set: u16,
+    modes_reset: u16,
+    formatted: bool,
+    // Set for guards of stdout and stderr, whose changes the panic hook undoes
+    stream: Option<Stream>,
+}
+
+impl Guard<io::Stdout> {
+    /// Creates a guard for stdout
+    ///
+    /// Changes made through it are also undone by the [panic hook](install_panic_hook).
+    #[inline]
+    pub fn stdout() -> Self {
+        let mut guard = Self::new(io::stdout());
+        guard.stream = Some(Stream::Stdout);
+        guard
+    }
+}
+
+impl Guard<io::Stderr> {
+    /// Creates a guard for stderr
+    ///
+    /// Changes made through it are also undone by the [panic hook](install_panic_hook).
+    #[inline]
+    pub fn stderr() -> Self {
+        let mut guard = Self::new(io::stderr());
+        guard.stream = Some(Stream::Stderr);
+        guard
+    }
+}
+
+impl<W: Write> Guard<W> {
+    /// Creates a guard wrapping `out`
+    #[inline]
+    pub fn new(out: W) -> Self {
+        Self { out: Some(out), modes_set: 0, modes_reset: 0, formatted: false, stream: None }
+    }
+
+    /// Sets (enables) a mode, to be reset on drop
+    pub fn set_mode(&mut self, mode: Mode) -> io::Result<()> {
+        self.inner().write_all(mode.set().as_bytes())?;
+        let previous = self.state();
+        let bit = bit(mode);
+        match self.modes_reset & bit != 0 {
+            // Undoing an earlier reset, so nothing now needs to be undone
+            true => self.modes_reset &= !bit,
+            false => self.modes_set |= bit,
+        }
+        self.publish(previous);
+        Ok(())
+    }
+
+    /// Resets (disables) a mode, to be set again on drop
+    ///
+    /// This is for modes that are normally set, such as cursor visibility.
+    pub fn reset_mode(&mut self, mode: Mode) -> io::Result<()> {
+        self.inner().write_all(mode.reset().as_bytes())?;
+        let previous = self.state();
+        let bit = bit(mode);
+        match self.modes_set & bit != 0 {
+            // Undoing an earlier set, so nothing now needs to be undone
+            true => self.modes_set &= !bit,
+            false => self.modes_reset |= bit,
+        }
+        self.publish(previous);
+        Ok(())
+    }
+
+    /// Restores the terminal state now, rather than waiting for drop
+    pub fn restore(&mut self) -> io::Result<()> {
+        let previous = self.state();
+        write_restore(self.inner(), previous)?;
+        self.modes_set = 0;
+        self.modes_reset = 0;
+        self.formatted = false;
+        self.publish(previous);
+        self.inner().flush()
+    }
+
+    /// Restores the terminal state, then unwraps the guard, returning the underlying writer
+    pub fn into_inner(mut self) -> W {
+        // Any error has nowhere to go, just as when dropped
+        let _ = self.restore();
+        self.out.take().expect("guard used after into_inner")
+    }
+
+    /// Gets a mutable reference to the underlying writer
+    ///
+    /// Note that changes written directly to the underlying writer are not recorded.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner()
+    }
+
+    #[inline]
+    fn inner(&mut self) -> &mut W {
+        self.out.as_mut().expect("guard used after into_inner")
+    }
+
+    #[inline]
+    fn state(&self) -> (u16, u16, bool) {
+        (self.modes_set, self.modes_reset, self.formatted)
+    }
+
+    /// Updates the record used by the panic hook, replacing the given previous state
+    fn publish(&self, previous: (u16, u16, bool)) {
+        if let Some(stream) = self.stream {
+            // Adding before removing, so common changes are never missing from the record
+            stream.record().update(self.state(), true);
+            stream.record().update(previous, false);
+        }
+    }
+}
+
+impl<W: Write> Write for Guard<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner().write(buf)?;
+        if !self.formatted && buf[..written].contains(&0x1B) {
+            let previous = self.state();
+            self.formatted = true;
+            self.publish(previous);
+        }
+        Ok(written)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner().flush()
+    }
+}
+
+impl<W: Write> Drop for Guard<W> {
+    fn drop(&mut self) {
+        if self.out.is_some() {
+            let _ = self.restore();
+        }
+    }
+}
+
+/// Installs a panic hook that restores terminal state
+///
+/// The hook writes whatever is needed to undo the changes made through guards of stdout and
+/// stderr (those created with [`Guard::stdout()`] and [`Guard::stderr()`]) not yet dropped, to
+/// stdout and stderr respectively, then calls the previously installed hook (typically the default
+/// one that prints the panic message). Changes made through other guards are not its concern.
+/// Installing it more than once has no further effect.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let mut out = io::stdout();
+            let _ = write_restore(&mut out, STDOUT.get());
+            let _ = out.flush();
+            let mut out = io::stderr();
+            let _ = write_restore(&mut out, STDERR.get());
+            let _ = out.flush();
+            previous(info);
+        }));
+    });
+}
+
+/// Writes the sequences undoing recorded changes
+fn write_restore<W: Write>(out: &mut W, (set, reset, formatted): (u16, u16, bool))
+    -> io::Result<()>
+{
+    if formatted {
+        out.write_all(RESET.as_bytes())?;
+    }
+    for &mode in Mode::ALL.iter().rev() {
+        if set & bit(mode) != 0 {
+            out.write_all(mode.reset().as_bytes())?;
+        }
+        if reset & bit(mode) != 0 {
+            out.write_all(mode.set().as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Gets the bit representing a mode in a set of modes
+#[inline]
+fn bit(mode: Mode) -> u16 {
+    1 << Mode::ALL.iter().position(|&m| m == mode).unwrap_or(0)
+}
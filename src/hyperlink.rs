@@ -0,0 +1,107 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Hyperlinks
+//!
+//! Many modern terminals support making text a clickable link, through the OSC 8 sequence. The
+//! [`Hyperlink`] type here writes out some text wrapped in the sequences that do this.
+//!
+//! ```rust
+//! use term_ctrl::hyperlink::Hyperlink;
+//!
+//! let link = Hyperlink::new("https://example.com/issues/42", "#42").enabled(true);
+//! assert_eq!("\u{1B}]8;;https://example.com/issues/42\u{1B}\\#42\u{1B}]8;;\u{1B}\\",
+//!     format!("{}", link));
+//!
+//! let link = link.enabled(false);
+//! assert_eq!("#42", format!("{}", link));
+//! ```
+//!
+//! Terminals that do not support hyperlinks should simply ignore the sequences, displaying just the
+//! text. As with formatting though, the sequences should not be used where output is not connected
+//! to a terminal; by default a hyperlink is only written out in full if stdout is connected to a
+//! terminal (as per [`support::fmt_supported_stdout()`]).
+//!
+//! See the [specification][spec] for details.
+//!
+//! [spec]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+
+use core::fmt;
+use crate::osc::{self, Terminator, OSC_PREFIX};
+use crate::support;
+
+/// Hyperlinked text
+///
+/// Note that the URI is written out percent-encoded where it contains anything other than
+/// printable ASCII chars (including space), which the specification does not allow. Being
+/// intended for full URIs, a file path should be given as a `file://` URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hyperlink<'a> {
+    uri: &'a str,
+    text: &'a str,
+    id: Option<&'a str>,
+    terminator: Terminator,
+    enabled: bool,
+}
+
+impl<'a> Hyperlink<'a> {
+    /// Creates a hyperlink
+    ///
+    /// The link is enabled according to [`support::fmt_supported_stdout()`].
+    pub fn new(uri: &'a str, text: &'a str) -> Self {
+        Self {
+            uri,
+            text,
+            id: None,
+            terminator: Terminator::default(),
+            enabled: support::fmt_supported_stdout(),
+        }
+    }
+
+    /// Sets the link ID
+    ///
+    /// Terminals may use this to highlight as one the separate parts of a link broken up, for
+    /// instance by wrapping or by the drawing of a full-screen program, where each part is given
+    /// the same URI and ID. Any chars not allowed in an ID are written out percent-encoded.
+    #[inline]
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Sets whether the link is enabled, i.e. written out in full rather than as plain text
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+impl<'a> fmt::Display for Hyperlink<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.enabled {
+            return f.write_str(self.text);
+        }
+        write!(f, "{}8;", OSC_PREFIX)?;
+        if let Some(id) = self.id {
+            f.write_str("id=")?;
+            // Params are colon separated, and the params are separated from the URI by a semi-colon
+            osc::write_percent_encoded(f, id, b":;")?;
+        }
+        f.write_str(";")?;
+        osc::write_percent_encoded(f, self.uri, b"")?;
+        write!(f, "{}{}{}8;;{}", self.terminator, self.text, OSC_PREFIX, self.terminator)
+    }
+}
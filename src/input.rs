@@ -0,0 +1,541 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Input decoding
+//!
+//! With the terminal in raw mode (see [`RawMode`]), input arrives as the raw bytes sent by the
+//! terminal, with keys such as the arrow keys, mouse reports (where enabled), and so on, all
+//! encoded as escape sequences. The [`Parser`] here decodes such input into [`Event`]s.
+//!
+//! Bytes are fed in as they are read, and events taken out as they become complete; a sequence
+//! split across reads is simply held until the rest arrives.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! use term_ctrl::input::{Parser, Event, Key, KeyEvent, Modifiers};
+//!
+//! let mut parser = Parser::new();
+//! parser.feed(b"a\x1B[1;5");
+//! assert_eq!(Some(Event::Key(KeyEvent::new(Key::Char('a'), Modifiers::NONE))), parser.next());
+//! assert_eq!(None, parser.next());
+//! parser.feed(b"A");
+//! assert_eq!(Some(Event::Key(KeyEvent::new(Key::Up, Modifiers::CTRL))), parser.next());
+//! # }
+//! ```
+//!
+//! Note that the escape key sends a lone ESC byte, which is also how every escape sequence
+//! starts. The parser thus cannot tell the escape key from the start of a sequence until more
+//! input arrives. The typical approach, where no more input arrives within a short time (say
+//! 50ms), is to call [`Parser::flush()`], which takes whatever is held as complete.
+//!
+//! Mouse reports are decoded in the SGR format only, as enabled with the `MOUSE_SGR` mode (along
+//! with one of the other mouse modes). Bracketed paste and focus reporting likewise need their
+//! respective modes enabling (see the [`modes`](mod@crate::modes) mod). Keys reported with the
+//! kitty keyboard protocol, where enabled (see the [`keyboard`](mod@crate::keyboard) mod), are
+//! decoded too.
+//!
+//! The parser, and the [`Event`] type it gives, require the `alloc` feature; the key and mouse
+//! types do not.
+//!
+//! [`RawMode`]: crate::support::RawMode
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::mem;
+use core::ops::{BitOr, BitOrAssign};
+#[cfg(feature = "alloc")]
+use crate::keyboard;
+
+#[cfg(feature = "alloc")]
+const ESC: u8 = 0x1B;
+/// Sequence ending a bracketed paste
+#[cfg(feature = "alloc")]
+const PASTE_END: &[u8] = b"\x1B[201~";
+
+/// Input event
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// Key press
+    Key(KeyEvent),
+    /// Mouse button, wheel or motion
+    Mouse(MouseEvent),
+    /// Pasted text (where bracketed paste is enabled)
+    Paste(String),
+    /// The terminal gained focus (where focus reporting is enabled)
+    FocusIn,
+    /// The terminal lost focus (where focus reporting is enabled)
+    FocusOut,
+    /// Unrecognised input
+    Unknown(Vec<u8>),
+}
+
+/// Key press event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub modifiers: Modifiers,
+    /// Kind of event (only ever a press unless the kitty keyboard protocol is used to have repeat
+    /// and release events reported; see the [`keyboard`](mod@crate::keyboard) mod)
+    pub kind: KeyKind,
+}
+
+impl KeyEvent {
+    /// Creates a new key press event
+    #[inline]
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        Self { key, modifiers, kind: KeyKind::Press }
+    }
+}
+
+/// Kind of key event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyKind {
+    Press,
+    Repeat,
+    Release,
+}
+
+impl Default for KeyKind {
+    #[inline]
+    fn default() -> Self {
+        KeyKind::Press
+    }
+}
+
+/// Key
+///
+/// Control chars are given as the corresponding `Char` with the `CTRL` modifier, e.g. `0x01` as
+/// Ctrl+A (lower case `a`), except where they correspond to a key of their own, such as Enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    /// Function key (F1 being `F(1)`)
+    F(u8),
+}
+
+/// A set of modifier keys
+///
+/// Sets can be combined with the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    /// No modifiers
+    pub const NONE:  Modifiers = Modifiers(0);
+    /// Shift
+    pub const SHIFT: Modifiers = Modifiers(1 << 0);
+    /// Alt (or Option)
+    pub const ALT:   Modifiers = Modifiers(1 << 1);
+    /// Ctrl
+    pub const CTRL:  Modifiers = Modifiers(1 << 2);
+    /// Meta (on terminals that distinguish it from Alt)
+    pub const META:  Modifiers = Modifiers(1 << 3);
+    /// Super (only reported with the kitty keyboard protocol)
+    pub const SUPER: Modifiers = Modifiers(1 << 4);
+    /// Hyper (only reported with the kitty keyboard protocol)
+    pub const HYPER: Modifiers = Modifiers(1 << 5);
+
+    /// Is the set empty?
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Does this set contain all of the modifiers in `other`?
+    #[inline]
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds the modifiers in `other` to this set
+    #[inline]
+    pub fn insert(&mut self, other: Modifiers) {
+        self.0 |= other.0;
+    }
+
+    /// Removes the modifiers in `other` from this set
+    #[inline]
+    pub fn remove(&mut self, other: Modifiers) {
+        self.0 &= !other.0;
+    }
+
+    /// Gets the modifiers from an xterm style modifier parameter (one plus the modifier bits)
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_param(param: Option<u16>) -> Self {
+        Modifiers(param.unwrap_or(1).saturating_sub(1) as u8 & 0b1111)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    #[inline]
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Modifiers) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Mouse event
+///
+/// Rows and columns are one-based, as with cursor positioning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    pub kind: MouseKind,
+    pub row: u16,
+    pub col: u16,
+    /// Modifiers (the terminal reports only shift, alt and ctrl, and may not report all of them)
+    pub modifiers: Modifiers,
+}
+
+/// Kind of mouse event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseKind {
+    Press(MouseButton),
+    Release(MouseButton),
+    /// Motion with a button held
+    Drag(MouseButton),
+    /// Motion with no button held
+    Move,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// Mouse button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+/// Incremental input parser
+///
+/// Events are taken out through the [`Iterator`] implementation, which gives `None` once no
+/// complete event remains (at which point more can be fed in and iteration resumed).
+///
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    buf: Vec<u8>,
+    /// Pasted text collected so far, while within a bracketed paste
+    paste: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "alloc")]
+/// Result of decoding
+enum Decoded {
+    Event(Event),
+    PasteStart,
+}
+
+#[cfg(feature = "alloc")]
+impl Parser {
+    /// Creates a new parser
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds input to be parsed
+    #[inline]
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Gets the next event, taking any incomplete sequence held as complete
+    ///
+    /// This is to be used where no more input has arrived within a short time, to resolve a lone
+    /// ESC as the escape key. ESC followed by just `[` or `O` is taken as that char with Alt, and
+    /// any other incomplete sequence given as [`Event::Unknown`]. Note that an incomplete
+    /// bracketed paste is left alone.
+    pub fn flush(&mut self) -> Option<Event> {
+        if let Some(event) = self.next() {
+            return Some(event);
+        }
+        if self.paste.is_some() || self.buf.is_empty() {
+            return None;
+        }
+        let bytes = mem::replace(&mut self.buf, Vec::new());
+        Some(match bytes[..] {
+            [ESC] => key(Key::Escape, Modifiers::NONE),
+            [ESC, b @ b'['] | [ESC, b @ b'O'] => key(Key::Char(b as char), Modifiers::ALT),
+            _ => Event::Unknown(bytes),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Parser {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(paste) = &mut self.paste {
+                match find(&self.buf, PASTE_END) {
+                    Some(i) => {
+                        paste.extend(self.buf.drain(..i));
+                        self.buf.drain(..PASTE_END.len());
+                        let paste = self.paste.take().unwrap_or_default();
+                        return Some(Event::Paste(String::from_utf8_lossy(&paste).into_owned()));
+                    },
+                    None => {
+                        // Hold back anything that could be the start of the end sequence
+                        let buf = &self.buf;
+                        let keep = (1..PASTE_END.len()).rev()
+                            .find(|&n| buf.ends_with(&PASTE_END[..n]))
+                            .unwrap_or(0);
+                        let take = self.buf.len() - keep;
+                        paste.extend(self.buf.drain(..take));
+                        return None;
+                    },
+                }
+            }
+            let (len, decoded) = decode(&self.buf)?;
+            self.buf.drain(..len);
+            match decoded {
+                Decoded::Event(event) => return Some(event),
+                Decoded::PasteStart => self.paste = Some(Vec::new()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+fn key(key: Key, modifiers: Modifiers) -> Event {
+    Event::Key(KeyEvent::new(key, modifiers))
+}
+
+#[cfg(feature = "alloc")]
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+#[cfg(feature = "alloc")]
+/// Decodes the event at the start of `buf`, giving its length along with it
+///
+/// Gives `None` if `buf` is empty or holds only an incomplete sequence.
+fn decode(buf: &[u8]) -> Option<(usize, Decoded)> {
+    if *buf.first()? != ESC {
+        return decode_char(buf).map(|(len, event)| (len, Decoded::Event(event)));
+    }
+    match *buf.get(1)? {
+        b'[' => decode_csi(buf),
+        b'O' => {
+            let event = match *buf.get(2)? {
+                b'A' => key(Key::Up, Modifiers::NONE),
+                b'B' => key(Key::Down, Modifiers::NONE),
+                b'C' => key(Key::Right, Modifiers::NONE),
+                b'D' => key(Key::Left, Modifiers::NONE),
+                b'H' => key(Key::Home, Modifiers::NONE),
+                b'F' => key(Key::End, Modifiers::NONE),
+                b @ b'P'..=b'S' => key(Key::F(b - b'P' + 1), Modifiers::NONE),
+                _ => Event::Unknown(buf[..3].to_vec()),
+            };
+            Some((3, Decoded::Event(event)))
+        },
+        // Escape pressed twice, or before some sequence
+        ESC => Some((1, Decoded::Event(key(Key::Escape, Modifiers::NONE)))),
+        // Alt with some other key
+        _ => {
+            let (len, event) = decode_char(&buf[1..])?;
+            let event = match event {
+                Event::Key(mut key) => {
+                    key.modifiers.insert(Modifiers::ALT);
+                    Event::Key(key)
+                },
+                _ => Event::Unknown(buf[..len + 1].to_vec()),
+            };
+            Some((len + 1, Decoded::Event(event)))
+        },
+    }
+}
+
+/// Decodes a (non-ESC) char at the start of `buf`
+#[cfg(feature = "alloc")]
+fn decode_char(buf: &[u8]) -> Option<(usize, Event)> {
+    let ctrl = |c: u8| key(Key::Char(c as char), Modifiers::CTRL);
+    let event = match buf[0] {
+        b'\r' | b'\n' => key(Key::Enter, Modifiers::NONE),
+        b'\t' => key(Key::Tab, Modifiers::NONE),
+        0x7F | 0x08 => key(Key::Backspace, Modifiers::NONE),
+        0x00 => ctrl(b' '),
+        b @ 0x01..=0x1A => ctrl(b'a' + b - 0x01),
+        b @ 0x1C..=0x1F => ctrl(b'\\' + b - 0x1C),
+        b @ 0x20..=0x7E => key(Key::Char(b as char), Modifiers::NONE),
+        b => {
+            let len = match b {
+                0xC2..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF4 => 4,
+                _ => return Some((1, Event::Unknown(buf[..1].to_vec()))),
+            };
+            if buf.len() < len {
+                return None;
+            }
+            return Some(match core::str::from_utf8(&buf[..len]) {
+                Ok(s) => (len, key(Key::Char(s.chars().next()?), Modifiers::NONE)),
+                Err(_) => (1, Event::Unknown(buf[..1].to_vec())),
+            });
+        },
+    };
+    Some((1, event))
+}
+
+/// Decodes a CSI sequence at the start of `buf`
+#[cfg(feature = "alloc")]
+fn decode_csi(buf: &[u8]) -> Option<(usize, Decoded)> {
+    // Find the final byte, after any parameter and intermediate bytes
+    let mut end = 2;
+    loop {
+        match *buf.get(end)? {
+            0x20..=0x3F => end += 1,
+            0x40..=0x7E => break,
+            // Malformed; give up on it at this point
+            _ => return Some((end, Decoded::Event(Event::Unknown(buf[..end].to_vec())))),
+        }
+    }
+    let len = end + 1;
+    let unknown = || Some((len, Decoded::Event(Event::Unknown(buf[..len].to_vec()))));
+    // Only ASCII bytes have been accepted
+    let params = core::str::from_utf8(&buf[2..end]).ok()?;
+    let fin = buf[end];
+
+    if params.starts_with('<') {
+        return match mouse(&params[1..], fin) {
+            Some(event) => Some((len, Decoded::Event(Event::Mouse(event)))),
+            None => unknown(),
+        };
+    }
+    if !params.bytes().all(|b| b.is_ascii_digit() || b == b';' || b == b':') {
+        return unknown();
+    }
+    if fin == b'u' {
+        let report = keyboard::parse_report_params(params);
+        return match report.and_then(|r| r.key().map(|k| (k, r))) {
+            Some((k, report)) => {
+                let event = KeyEvent { key: k, modifiers: report.modifiers, kind: report.kind };
+                Some((len, Decoded::Event(Event::Key(event))))
+            },
+            None => unknown(),
+        };
+    }
+    // Parameters may have colon separated sub-parameters, as used by the kitty keyboard protocol
+    // to report the kind of key event
+    let sub_param = |i, j| {
+        params.split(';').nth(i)
+            .and_then(|p: &str| p.split(':').nth(j))
+            .and_then(|p| p.parse::<u16>().ok())
+    };
+    let param = |i| sub_param(i, 0);
+    let modifiers = Modifiers::from_param(param(1));
+    let kind = match sub_param(1, 1) {
+        None | Some(1) => KeyKind::Press,
+        Some(2) => KeyKind::Repeat,
+        Some(3) => KeyKind::Release,
+        _ => return unknown(),
+    };
+    let key = |key, modifiers| Event::Key(KeyEvent { key, modifiers, kind });
+    let event = match fin {
+        b'~' => {
+            let k = match param(0) {
+                Some(1) | Some(7) => Key::Home,
+                Some(2) => Key::Insert,
+                Some(3) => Key::Delete,
+                Some(4) | Some(8) => Key::End,
+                Some(5) => Key::PageUp,
+                Some(6) => Key::PageDown,
+                Some(n @ 11..=15) => Key::F(n as u8 - 10),
+                Some(n @ 17..=21) => Key::F(n as u8 - 11),
+                Some(n @ 23..=24) => Key::F(n as u8 - 12),
+                Some(200) => return Some((len, Decoded::PasteStart)),
+                _ => return unknown(),
+            };
+            key(k, modifiers)
+        },
+        b'A' => key(Key::Up, modifiers),
+        b'B' => key(Key::Down, modifiers),
+        b'C' => key(Key::Right, modifiers),
+        b'D' => key(Key::Left, modifiers),
+        b'H' => key(Key::Home, modifiers),
+        b'F' => key(Key::End, modifiers),
+        b @ b'P'..=b'S' => key(Key::F(b - b'P' + 1), modifiers),
+        b'Z' if params.is_empty() => key(Key::Tab, Modifiers::SHIFT),
+        b'I' if params.is_empty() => Event::FocusIn,
+        b'O' if params.is_empty() => Event::FocusOut,
+        _ => return unknown(),
+    };
+    Some((len, Decoded::Event(event)))
+}
+
+/// Decodes the parameters and final byte of an SGR mouse report
+#[cfg(feature = "alloc")]
+fn mouse(params: &str, fin: u8) -> Option<MouseEvent> {
+    let mut params = params.split(';').map(|p| p.parse::<u16>().ok());
+    let (cb, col, row) = (params.next()??, params.next()??, params.next()??);
+    if params.next().is_some() {
+        return None;
+    }
+    let mut modifiers = Modifiers::NONE;
+    for &(bit, modifier) in &[(4, Modifiers::SHIFT), (8, Modifiers::ALT), (16, Modifiers::CTRL)] {
+        if cb & bit != 0 {
+            modifiers.insert(modifier);
+        }
+    }
+    let button = match cb & 0b11 {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    };
+    let kind = match (cb & 0b1100_0000, cb & 32 != 0, button, fin) {
+        (64, _, _, b'M') => match cb & 0b11 {
+            0 => MouseKind::ScrollUp,
+            1 => MouseKind::ScrollDown,
+            2 => MouseKind::ScrollLeft,
+            _ => MouseKind::ScrollRight,
+        },
+        (0, true, Some(button), _) => MouseKind::Drag(button),
+        (0, true, None, _) => MouseKind::Move,
+        (0, false, Some(button), b'M') => MouseKind::Press(button),
+        (0, false, Some(button), b'm') => MouseKind::Release(button),
+        _ => return None,
+    };
+    Some(MouseEvent { kind, row, col, modifiers })
+}
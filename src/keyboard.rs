@@ -0,0 +1,252 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Kitty keyboard protocol
+//!
+//! The traditional encoding of keys leaves many combinations indistinguishable, such as Ctrl+I and
+//! Tab, and reports nothing of keys being released. The kitty keyboard protocol, supported by a
+//! growing number of terminals, addresses this. Programs enable progressive enhancements of the key
+//! encoding, as a set of [`Flags`], pushing them onto a stack kept by the terminal, and popping
+//! them off again when done.
+//!
+//! ```rust
+//! use term_ctrl::keyboard::{Flags, Push, Pop};
+//!
+//! let flags = Flags::DISAMBIGUATE | Flags::REPORT_EVENT_TYPES;
+//! print!("{}", Push(flags));
+//! // ...
+//! print!("{}", Pop(1));
+//! assert_eq!("\u{1B}[>3u", format!("{}", Push(flags)));
+//! ```
+//!
+//! Keys are then reported with sequences of the form
+//! `CSI unicode-key-code:alternates ; modifiers:event-type ; text u`, which the
+//! [`input`](mod@crate::input) mod parser decodes (the details being available through
+//! [`parse_report()`]). Terminals not supporting the protocol ignore the push sequence; whether
+//! supported can be checked by sending [`QUERY`] and seeing whether it gets a reply.
+//!
+//! See the [specification][spec] for details.
+//!
+//! [spec]: https://sw.kovidgoyal.net/kitty/keyboard-protocol/
+
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+use crate::input::{Key, KeyKind, Modifiers};
+
+/// Queries the current flags
+///
+/// A terminal supporting the protocol replies with a sequence that [`parse_flags_reply()`] can
+/// decode.
+pub const QUERY: &str = "\u{1B}[?u";
+
+/// A set of progressive enhancement flags
+///
+/// Sets can be combined with the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Flags(u8);
+
+impl Flags {
+    /// No enhancements
+    pub const NONE:                  Flags = Flags(0);
+    /// Disambiguate escape codes (e.g. Ctrl+I from Tab, and Alt+key from ESC then key)
+    pub const DISAMBIGUATE:          Flags = Flags(1 << 0);
+    /// Report key repeat and release events
+    pub const REPORT_EVENT_TYPES:    Flags = Flags(1 << 1);
+    /// Report the shifted and base layout keys as alternates
+    pub const REPORT_ALTERNATE_KEYS: Flags = Flags(1 << 2);
+    /// Report all keys, including those producing text (and Enter, Tab and Backspace), as escape
+    /// codes
+    pub const REPORT_ALL_KEYS:       Flags = Flags(1 << 3);
+    /// Report the text a key produces, along with the key
+    pub const REPORT_TEXT:           Flags = Flags(1 << 4);
+
+    /// Gets the flags as their numeric value
+    #[inline]
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Gets flags from their numeric value, ignoring unknown flags
+    #[inline]
+    pub fn from_bits(bits: u8) -> Self {
+        Flags(bits & 0b1_1111)
+    }
+
+    /// Is the set empty?
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Does this set contain all of the flags in `other`?
+    #[inline]
+    pub fn contains(self, other: Flags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds the flags in `other` to this set
+    #[inline]
+    pub fn insert(&mut self, other: Flags) {
+        self.0 |= other.0;
+    }
+
+    /// Removes the flags in `other` from this set
+    #[inline]
+    pub fn remove(&mut self, other: Flags) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+
+    #[inline]
+    fn bitor(self, rhs: Flags) -> Flags {
+        Flags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Flags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Flags) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Pushes flags onto the stack, making them the current flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Push(pub Flags);
+
+impl fmt::Display for Push {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[>{}u", self.0.bits())
+    }
+}
+
+/// Pops a number of entries off the stack, restoring the flags in use before they were pushed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pop(pub u16);
+
+impl fmt::Display for Pop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[<{}u", self.0)
+    }
+}
+
+/// Parses a reply to a [`QUERY`], giving the current flags
+///
+/// The reply is expected to be the complete sequence, `CSI ? flags u`.
+pub fn parse_flags_reply(reply: &str) -> Option<Flags> {
+    if !reply.starts_with("\u{1B}[?") || !reply.ends_with('u') {
+        return None;
+    }
+    let flags = &reply[3..(reply.len() - 1)];
+    match flags.bytes().all(|b| b.is_ascii_digit()) {
+        true => flags.parse().ok().map(Flags::from_bits),
+        false => None,
+    }
+}
+
+/// Key report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyReport {
+    /// The unicode key code (for keys producing text, the lower case char, otherwise a code for
+    /// the key, from the unicode private use area in most cases)
+    pub code: u32,
+    /// The shifted key, where alternate keys are reported and shift is held
+    pub shifted: Option<u32>,
+    /// The key in the standard (PC-101) layout, where alternate keys are reported and it differs
+    pub base: Option<u32>,
+    /// Modifiers (lock key states are not included)
+    pub modifiers: Modifiers,
+    pub kind: KeyKind,
+}
+
+impl KeyReport {
+    /// Gets the key reported, where it is one that [`Key`] can represent
+    pub fn key(&self) -> Option<Key> {
+        Some(match self.code {
+            9 => Key::Tab,
+            13 => Key::Enter,
+            27 => Key::Escape,
+            127 => Key::Backspace,
+            // F13 to F35
+            c @ 57376..=57398 => Key::F((c - 57376 + 13) as u8),
+            // Other functional keys use codes from the private use area
+            c => match core::char::from_u32(c) {
+                Some(c) if c.is_control() || ('\u{E000}'..='\u{F8FF}').contains(&c) => return None,
+                Some(c) => Key::Char(c),
+                None => return None,
+            },
+        })
+    }
+}
+
+/// Parses a key report, as a complete `CSI ... u` sequence
+///
+/// ```rust
+/// use term_ctrl::input::{Key, KeyKind, Modifiers};
+/// use term_ctrl::keyboard::parse_report;
+///
+/// let report = parse_report("\u{1B}[105;5u").unwrap();
+/// assert_eq!(Some(Key::Char('i')), report.key());
+/// assert_eq!(Modifiers::CTRL, report.modifiers);
+/// assert_eq!(KeyKind::Press, report.kind);
+/// ```
+pub fn parse_report(seq: &str) -> Option<KeyReport> {
+    if !seq.starts_with("\u{1B}[") || !seq.ends_with('u') {
+        return None;
+    }
+    parse_report_params(&seq[2..(seq.len() - 1)])
+}
+
+/// Parses the parameters of a key report
+pub(crate) fn parse_report_params(params: &str) -> Option<KeyReport> {
+    if !params.bytes().all(|b| b.is_ascii_digit() || b == b';' || b == b':') {
+        return None;
+    }
+    let number = |s: &str| s.parse::<u32>().ok();
+    let mut params = params.split(';');
+    let mut codes = params.next()?.split(':');
+    let code = number(codes.next()?)?;
+    let shifted = codes.next().and_then(number);
+    let base = codes.next().and_then(number);
+    let (modifiers, kind) = match params.next() {
+        Some(p) => {
+            let mut parts = p.split(':');
+            let modifiers = parts.next().and_then(number).unwrap_or(1).checked_sub(1)?;
+            (modifiers, parts.next().and_then(number).unwrap_or(1))
+        },
+        None => (0, 1),
+    };
+    Some(KeyReport {
+        code,
+        shifted,
+        base,
+        modifiers: modifiers_from_bits(modifiers),
+        kind: match kind {
+            1 => KeyKind::Press,
+            2 => KeyKind::Repeat,
+            3 => KeyKind::Release,
+            _ => return None,
+        },
+    })
+}
+
+/// Gets modifiers from the protocol’s modifier bits
+fn modifiers_from_bits(bits: u32) -> Modifiers {
+    let mut modifiers = Modifiers::NONE;
+    for &(bit, modifier) in &[(1, Modifiers::SHIFT), (2, Modifiers::ALT), (4, Modifiers::CTRL),
+        (8, Modifiers::SUPER), (16, Modifiers::HYPER), (32, Modifiers::META)]
+    {
+        if bits & bit != 0 {
+            modifiers.insert(modifier);
+        }
+    }
+    modifiers
+}
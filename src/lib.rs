@@ -55,7 +55,24 @@
 //! Note, when resetting to normal, be sure to always use the proper reset sequence. Do not make the
 //! mistake of setting text colour to black and presuming that this achieves the same thing; it does
 //! not. (Consider that some people have black text on a white background in their terminal, whilst
-//! others use the opposite! Black text on a black background does not work very well!).
+//! others use the opposite! Black text on a black background does not work very well!). Where it
+//! matters, the terminal can be asked for its background colour, via the [palette mod], and
+//! colours chosen to suit it, via the [background mod].
+//!
+//! Alternatively, output can be written through the [`Writer`] type, which checks suitability once
+//! and then strips any sequences from what is written through it, if necessary:
+//!
+//! ```rust
+//! # #[cfg(feature = "std")] {
+//! use std::io::Write;
+//! use term_ctrl::predefined::{RESET, colours::fg::RED};
+//! let mut out = term_ctrl::writer::Writer::stdout(true);
+//! writeln!(out, "{}Error:{} You made an error!", RED, RESET).unwrap();
+//! # }
+//! ```
+//!
+//! Where text already contains sequences, such as that captured from another program, the
+//! [strip mod] offers helpers for removing them.
 //!
 //! # Constructing sequences
 //!
@@ -205,6 +222,74 @@
 //! starts with `48`, it is providing an RGB colour (the `2`), and is then followed with RGB values
 //! of `180` for red, `15` for green and `70` for blue.
 //!
+//! ### Underline styles and colour
+//!
+//! Many modern terminals extend underlining with a choice of style, given as a sub-parameter of
+//! code `4`, separated by a colon (not a semi-colon), and with a separate underline colour.
+//!
+//! <table>
+//!     <thead>
+//!         <tr><th>Code</th><th>Effect</th></tr>
+//!     </thead>
+//!     <tbody>
+//!         <tr><td>4:0</td><td>Underline off (as per <code>24</code>)</td></tr>
+//!         <tr><td>4:1</td><td>Underline on (as per <code>4</code>)</td></tr>
+//!         <tr><td>4:2</td><td>Double-underline on (as per <code>21</code>)</td></tr>
+//!         <tr><td>4:3</td><td>Curly (wavy) underline on</td></tr>
+//!         <tr><td>4:4</td><td>Dotted underline on</td></tr>
+//!         <tr><td>4:5</td><td>Dashed underline on</td></tr>
+//!         <tr><td>58</td><td>Extended underline colour</td></tr>
+//!         <tr><td>59</td><td>Default underline colour (reset)</td></tr>
+//!     </tbody>
+//! </table>
+//!
+//! Code `58` works just like `38` and `48` above, taking either a 256-colour or RGB selection.
+//! There are no basic palette codes for underline colour. The [`c256_ul`] and [`rgb_ul`] macros
+//! construct these code sets, for instance to get a red squiggle as commonly used to highlight
+//! spelling mistakes:
+//!
+//! ```rust
+//! use term_ctrl::{seq, rgb_ul};
+//! assert_eq!("\u{1B}[4:3;58;2;255;0;0m", seq!("4:3", rgb_ul!(255, 0, 0)));
+//! ```
+//!
+//! Note that underline styles and colour are extensions, not supported by all terminals. Terminals
+//! lacking support for underline styles typically fall back to a plain underline, though some older
+//! ones may misinterpret them entirely, so use them with care.
+//!
+//! # Typed styles
+//!
+//! Where a fixed string is not enough, for instance where a style is to be chosen at runtime from
+//! some configuration, the [`Style`] type can be used. It holds a selection of colours and effects
+//! and writes out the sequence applying them when formatted:
+//!
+//! ```rust
+//! use term_ctrl::predefined::RESET;
+//! use term_ctrl::style::Style;
+//! use term_ctrl::colour::{Colour, BasicColour};
+//! let style = Style::new().fg(Colour::Basic(BasicColour::Red)).bold();
+//! println!("{}Error:{} You made an error!", style, RESET);
+//! ```
+//!
+//! # Other sequences
+//!
+//! Beyond the formatting sequences that are the main focus of this crate, support is also offered
+//! for some other common terminal control sequences:
+//!
+//!  - Cursor movement and positioning, via the [cursor mod] and macros such as [`cursor_up`].
+//!  - Erasing of the screen or line, via the [erase mod].
+//!  - Setting and resetting of modes such as the alternate screen buffer, via the [modes mod].
+//!  - Hyperlinks, via the [hyperlink mod].
+//!  - Setting the window and tab titles, via the [title mod].
+//!  - Clipboard access, via the [clipboard mod].
+//!  - Setting and querying palette and default colours, via the [palette mod].
+//!  - Decoding of keyboard, mouse and other input, via the [input mod].
+//!  - The kitty keyboard protocol, via the [keyboard mod].
+//!  - Querying the terminal’s attributes and mode support, via the [query mod].
+//!
+//! To help avoid leaving the user’s terminal in an altered state on exit, or on panic, the
+//! [guard mod] offers a writer that undoes changes made through it when dropped.
+//!
 //! # Resources
 //!
 //! A collection of useful or informative related resources:
@@ -216,7 +301,25 @@
 //!
 //! [support mod]: mod@crate::support
 //! [predefined mod]: mod@crate::predefined
+//! [strip mod]: mod@crate::strip
 //! [`seq`]: macro@seq
+//! [`Style`]: crate::style::Style
+//! [`Writer`]: crate::writer::Writer
+//! [cursor mod]: mod@crate::cursor
+//! [`cursor_up`]: macro@cursor_up
+//! [`c256_ul`]: macro@c256_ul
+//! [`rgb_ul`]: macro@rgb_ul
+//! [erase mod]: mod@crate::erase
+//! [modes mod]: mod@crate::modes
+//! [guard mod]: mod@crate::guard
+//! [hyperlink mod]: mod@crate::hyperlink
+//! [title mod]: mod@crate::title
+//! [clipboard mod]: mod@crate::clipboard
+//! [palette mod]: mod@crate::palette
+//! [background mod]: mod@crate::background
+//! [input mod]: mod@crate::input
+//! [keyboard mod]: mod@crate::keyboard
+//! [query mod]: mod@crate::query
 //! [Xterm_256color_chart.svg]: https://upload.wikimedia.org/wikipedia/commons/1/15/Xterm_256color_chart.svg
 //! [wikipedia_ANSI_escape_code_SGR]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR
 //! [wikipedia_Xterm]: https://en.wikipedia.org/wiki/Xterm
@@ -224,11 +327,42 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 extern crate atty;
 #[cfg(windows)]
 extern crate winapi;
 
+pub mod background;
+pub mod clipboard;
 pub mod codes;
+pub mod colour;
+pub mod cursor;
+pub mod erase;
+#[cfg(feature = "std")]
+pub mod guard;
+pub mod hyperlink;
+pub mod input;
+pub mod keyboard;
 mod macros;
+pub mod modes;
+pub mod osc;
+pub mod palette;
+pub mod parse;
 pub mod predefined;
+pub mod query;
+mod scan;
+#[cfg(feature = "alloc")]
+pub mod strip;
+pub mod style;
 pub mod support;
+mod tables;
+pub mod title;
+pub mod width;
+#[cfg(feature = "std")]
+pub mod writer;
+
+// Alias for the non-British-English speakers
+pub use self::colour as color;
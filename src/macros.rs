@@ -86,6 +86,24 @@ macro_rules! c256_bg {
     ($col:expr) => { concat!("48;5;", $col) };
 }
 
+/// Constructs a 256-colour underline colour code set (to be used in a control sequence)
+///
+/// See [underline styles and colour](crate#underline-styles-and-colour).
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::{seq, c256_ul};
+/// // Get the codes
+/// assert_eq!("58;5;238", c256_ul!(238));
+/// // Use in a sequence
+/// assert_eq!("\u{1B}[58;5;238m", seq!(c256_ul!(238)));
+/// ```
+#[macro_export]
+macro_rules! c256_ul {
+    ($col:expr) => { concat!("58;5;", $col) };
+}
+
 /// Constructs an RGB foreground (text) colour code set (to be used in a control sequence)
 ///
 /// # Examples:
@@ -117,3 +135,191 @@ macro_rules! rgb_fg {
 macro_rules! rgb_bg {
     ($red:expr, $green:expr, $blue:expr) => { concat!("48;2;", $red, ";", $green, ";", $blue) };
 }
+
+/// Constructs an RGB underline colour code set (to be used in a control sequence)
+///
+/// See [underline styles and colour](crate#underline-styles-and-colour).
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::{seq, rgb_ul};
+/// // Get the RGB codes
+/// assert_eq!("58;2;180;15;70", rgb_ul!(180, 15, 70));
+/// // Use in a sequence
+/// assert_eq!("\u{1B}[4:3;58;2;180;15;70m", seq!("4:3", rgb_ul!(180, 15, 70)));
+/// ```
+#[macro_export]
+macro_rules! rgb_ul {
+    ($red:expr, $green:expr, $blue:expr) => { concat!("58;2;", $red, ";", $green, ";", $blue) };
+}
+
+/// Constructs a cursor up (CUU) sequence
+///
+/// Moves the cursor up by the given number of lines (one if not specified), stopping at the top.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_up;
+/// assert_eq!("\u{1B}[3A", cursor_up!(3));
+/// assert_eq!("\u{1B}[A", cursor_up!());
+/// ```
+#[macro_export]
+macro_rules! cursor_up {
+    ($n:expr) => { concat!("\u{1B}[", $n, "A") };
+    () => { "\u{1B}[A" };
+}
+
+/// Constructs a cursor down (CUD) sequence
+///
+/// Moves the cursor down by the given number of lines (one if not specified), stopping at the
+/// bottom.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_down;
+/// assert_eq!("\u{1B}[3B", cursor_down!(3));
+/// assert_eq!("\u{1B}[B", cursor_down!());
+/// ```
+#[macro_export]
+macro_rules! cursor_down {
+    ($n:expr) => { concat!("\u{1B}[", $n, "B") };
+    () => { "\u{1B}[B" };
+}
+
+/// Constructs a cursor forward (CUF) sequence
+///
+/// Moves the cursor right by the given number of columns (one if not specified), stopping at the
+/// right edge.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_forward;
+/// assert_eq!("\u{1B}[3C", cursor_forward!(3));
+/// assert_eq!("\u{1B}[C", cursor_forward!());
+/// ```
+#[macro_export]
+macro_rules! cursor_forward {
+    ($n:expr) => { concat!("\u{1B}[", $n, "C") };
+    () => { "\u{1B}[C" };
+}
+
+/// Constructs a cursor back (CUB) sequence
+///
+/// Moves the cursor left by the given number of columns (one if not specified), stopping at the
+/// left edge.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_back;
+/// assert_eq!("\u{1B}[3D", cursor_back!(3));
+/// assert_eq!("\u{1B}[D", cursor_back!());
+/// ```
+#[macro_export]
+macro_rules! cursor_back {
+    ($n:expr) => { concat!("\u{1B}[", $n, "D") };
+    () => { "\u{1B}[D" };
+}
+
+/// Constructs a cursor next line (CNL) sequence
+///
+/// Moves the cursor to the start of the line the given number of lines (one if not specified)
+/// down.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_next_line;
+/// assert_eq!("\u{1B}[2E", cursor_next_line!(2));
+/// assert_eq!("\u{1B}[E", cursor_next_line!());
+/// ```
+#[macro_export]
+macro_rules! cursor_next_line {
+    ($n:expr) => { concat!("\u{1B}[", $n, "E") };
+    () => { "\u{1B}[E" };
+}
+
+/// Constructs a cursor previous line (CPL) sequence
+///
+/// Moves the cursor to the start of the line the given number of lines (one if not specified) up.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_prev_line;
+/// assert_eq!("\u{1B}[2F", cursor_prev_line!(2));
+/// assert_eq!("\u{1B}[F", cursor_prev_line!());
+/// ```
+#[macro_export]
+macro_rules! cursor_prev_line {
+    ($n:expr) => { concat!("\u{1B}[", $n, "F") };
+    () => { "\u{1B}[F" };
+}
+
+/// Constructs a cursor horizontal absolute (CHA) sequence
+///
+/// Moves the cursor to the given column (one-based) of the current line.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_column;
+/// assert_eq!("\u{1B}[20G", cursor_column!(20));
+/// ```
+#[macro_export]
+macro_rules! cursor_column {
+    ($col:expr) => { concat!("\u{1B}[", $col, "G") };
+}
+
+/// Constructs a cursor position (CUP) sequence
+///
+/// Moves the cursor to the given row and column (both one-based).
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_pos;
+/// assert_eq!("\u{1B}[5;20H", cursor_pos!(5, 20));
+/// ```
+#[macro_export]
+macro_rules! cursor_pos {
+    ($row:expr, $col:expr) => { concat!("\u{1B}[", $row, ";", $col, "H") };
+}
+
+/// Constructs a horizontal and vertical position (HVP) sequence
+///
+/// This is equivalent to [`cursor_pos`](macro@cursor_pos), moving the cursor to the given row and
+/// column (both one-based).
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::cursor_hv_pos;
+/// assert_eq!("\u{1B}[5;20f", cursor_hv_pos!(5, 20));
+/// ```
+#[macro_export]
+macro_rules! cursor_hv_pos {
+    ($row:expr, $col:expr) => { concat!("\u{1B}[", $row, ";", $col, "f") };
+}
+
+/// Constructs an erase character (ECH) sequence
+///
+/// Erases the given number of chars (one if not specified) from the cursor onwards, within the
+/// line, without moving the cursor.
+///
+/// # Examples:
+///
+/// ```rust
+/// # use term_ctrl::erase_chars;
+/// assert_eq!("\u{1B}[5X", erase_chars!(5));
+/// assert_eq!("\u{1B}[X", erase_chars!());
+/// ```
+#[macro_export]
+macro_rules! erase_chars {
+    ($n:expr) => { concat!("\u{1B}[", $n, "X") };
+    () => { "\u{1B}[X" };
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! DEC private modes
+//!
+//! Terminals have a number of modes that programs can set (enable) and reset (disable), for
+//! instance switching to the alternate screen buffer as used by full-screen programs, or hiding
+//! the cursor. The [`Mode`] type here offers a selection of the commonly used ones, giving the
+//! sequences for setting and resetting them. The same sequences are available as constants in the
+//! `predefined::modes` mod.
+//!
+//! ```rust
+//! use term_ctrl::modes::Mode;
+//!
+//! print!("{}{}", Mode::AltScreen.set(), Mode::CursorVisible.reset());
+//! // ... draw stuff ...
+//! print!("{}{}", Mode::CursorVisible.set(), Mode::AltScreen.reset());
+//! assert_eq!("\u{1B}[?1049h", Mode::AltScreen.set());
+//! ```
+//!
+//! Remember to reset any modes you set before exiting, otherwise the user’s terminal may be left
+//! in an unusable state.
+
+use crate::predefined::modes::{self, reset};
+
+/// A DEC private mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Alternate screen buffer (`1049`)
+    AltScreen,
+    /// Cursor visibility (`25`); set by default
+    CursorVisible,
+    /// Auto-wrap (`7`); set by default
+    AutoWrap,
+    /// Bracketed paste (`2004`)
+    BracketedPaste,
+    /// Focus reporting (`1004`)
+    FocusReporting,
+    /// Synchronised output (`2026`)
+    SyncOutput,
+    /// Mouse click reporting (`1000`)
+    MouseClicks,
+    /// Mouse drag reporting (`1002`)
+    MouseDrag,
+    /// Mouse motion reporting (`1003`)
+    MouseMotion,
+    /// SGR extended mouse reporting format (`1006`)
+    MouseSgr,
+}
+
+/// Number of modes
+///
+/// Sets of modes are held as bits of a `u16` by the `guard` mod, so there can be no more than 16.
+pub(crate) const MODE_COUNT: usize = 10;
+
+impl Mode {
+    /// All of the modes
+    pub const ALL: [Mode; MODE_COUNT] = [
+        Mode::AltScreen, Mode::CursorVisible, Mode::AutoWrap, Mode::BracketedPaste,
+        Mode::FocusReporting, Mode::SyncOutput, Mode::MouseClicks, Mode::MouseDrag,
+        Mode::MouseMotion, Mode::MouseSgr,
+    ];
+
+    /// Gets the mode number
+    pub fn code(self) -> u16 {
+        match self {
+            Mode::AltScreen => 1049,
+            Mode::CursorVisible => 25,
+            Mode::AutoWrap => 7,
+            Mode::BracketedPaste => 2004,
+            Mode::FocusReporting => 1004,
+            Mode::SyncOutput => 2026,
+            Mode::MouseClicks => 1000,
+            Mode::MouseDrag => 1002,
+            Mode::MouseMotion => 1003,
+            Mode::MouseSgr => 1006,
+        }
+    }
+
+    /// Gets the sequence for setting (enabling) the mode
+    pub fn set(self) -> &'static str {
+        match self {
+            Mode::AltScreen => modes::ALT_SCREEN,
+            Mode::CursorVisible => modes::CURSOR_VISIBLE,
+            Mode::AutoWrap => modes::AUTO_WRAP,
+            Mode::BracketedPaste => modes::BRACKETED_PASTE,
+            Mode::FocusReporting => modes::FOCUS_REPORTING,
+            Mode::SyncOutput => modes::SYNC_OUTPUT,
+            Mode::MouseClicks => modes::MOUSE_CLICKS,
+            Mode::MouseDrag => modes::MOUSE_DRAG,
+            Mode::MouseMotion => modes::MOUSE_MOTION,
+            Mode::MouseSgr => modes::MOUSE_SGR,
+        }
+    }
+
+    /// Gets the sequence for resetting (disabling) the mode
+    pub fn reset(self) -> &'static str {
+        match self {
+            Mode::AltScreen => reset::ALT_SCREEN,
+            Mode::CursorVisible => reset::CURSOR_VISIBLE,
+            Mode::AutoWrap => reset::AUTO_WRAP,
+            Mode::BracketedPaste => reset::BRACKETED_PASTE,
+            Mode::FocusReporting => reset::FOCUS_REPORTING,
+            Mode::SyncOutput => reset::SYNC_OUTPUT,
+            Mode::MouseClicks => reset::MOUSE_CLICKS,
+            Mode::MouseDrag => reset::MOUSE_DRAG,
+            Mode::MouseMotion => reset::MOUSE_MOTION,
+            Mode::MouseSgr => reset::MOUSE_SGR,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Operating system commands
+//!
+//! Operating system command (OSC) sequences take the form `ESC ] <command> ; <params> <terminator>`
+//! and are used for things such as hyperlinks and setting the window title. This mod holds the
+//! common bits used by the various mods implementing specific commands.
+
+use core::fmt::{self, Write};
+
+/// The start of an operating system command sequence
+pub const OSC_PREFIX: &str = "\u{1B}]";
+
+/// Operating system command terminator
+///
+/// The standard terminator is ST (string terminator), `ESC \`. Some older terminals only understand
+/// BEL, which is widely supported as an alternative, and is the one xterm uses in its replies
+/// unless the query used ST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Terminator {
+    /// BEL (`\u{7}`)
+    Bel,
+    /// ST (`ESC \`)
+    St,
+}
+
+impl Default for Terminator {
+    #[inline]
+    fn default() -> Self {
+        Terminator::St
+    }
+}
+
+impl Terminator {
+    /// Gets the terminator string
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Terminator::Bel => "\u{7}",
+            Terminator::St => "\u{1B}\\",
+        }
+    }
+}
+
+impl fmt::Display for Terminator {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Gets the content of a reply, between the given prefix and either terminator
+pub(crate) fn reply_body<'a>(reply: &'a str, prefix: &str) -> Option<&'a str> {
+    if !reply.starts_with(prefix) {
+        return None;
+    }
+    let rest = &reply[prefix.len()..];
+    [Terminator::St, Terminator::Bel].iter()
+        .map(|t| t.as_str())
+        .find(|t| rest.ends_with(t))
+        .map(|t| &rest[..(rest.len() - t.len())])
+}
+
+/// Writes a string, percent-encoding control chars, space, non-ASCII bytes, and any extra ASCII
+/// chars specified
+pub(crate) fn write_percent_encoded(f: &mut fmt::Formatter, s: &str, extra: &[u8]) -> fmt::Result {
+    for b in s.bytes() {
+        match b {
+            0x21..=0x7E if !extra.contains(&b) => f.write_char(b as char)?,
+            _ => write!(f, "%{:02X}", b)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Palette and default colours
+//!
+//! Sequences for setting, querying and resetting the colours of the terminal’s 256 colour palette
+//! (OSC 4 and 104), and its default text and background colours (OSC 10 and 11, and 110 and 111).
+//!
+//! ```rust
+//! use term_ctrl::palette::{Set, Query, Target};
+//!
+//! // Make palette colour 1 (red) a little softer
+//! assert_eq!("\u{1B}]4;1;rgb:cc/33/33\u{1B}\\",
+//!     format!("{}", Set::new(Target::Index(1), (0xCC, 0x33, 0x33))));
+//! // Ask what the background colour is
+//! assert_eq!("\u{1B}]11;?\u{1B}\\", format!("{}", Query::new(Target::Background)));
+//! ```
+//!
+//! A terminal answers a query with a sequence giving the colour in the form
+//! `rgb:RRRR/GGGG/BBBB`, which [`parse_reply()`] can decode. Knowing the background colour is
+//! particularly useful for choosing colours that will be readable against it.
+
+use core::fmt;
+use crate::osc::{Terminator, OSC_PREFIX};
+
+/// Colour to set, query or reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// An entry in the 256 colour palette
+    Index(u8),
+    /// The default text colour
+    Foreground,
+    /// The default background colour
+    Background,
+}
+
+/// Error in parsing a reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Not a palette or default colour reply sequence
+    NotReply,
+    /// The colour is not in a recognised form
+    InvalidColour,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Error::NotReply => "not a colour reply sequence",
+            Error::InvalidColour => "invalid colour in reply",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Sequence setting a colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Set {
+    target: Target,
+    rgb: (u8, u8, u8),
+    terminator: Terminator,
+}
+
+impl Set {
+    /// Creates a sequence setting a colour to an RGB value
+    #[inline]
+    pub fn new(target: Target, rgb: (u8, u8, u8)) -> Self {
+        Self { target, rgb, terminator: Terminator::default() }
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl fmt::Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (r, g, b) = self.rgb;
+        write_command(f, self.target, false)?;
+        write!(f, ";rgb:{:02x}/{:02x}/{:02x}{}", r, g, b, self.terminator)
+    }
+}
+
+/// Sequence querying a colour
+///
+/// If the terminal supports it, it replies with a sequence that [`parse_reply()`] can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Query {
+    target: Target,
+    terminator: Terminator,
+}
+
+impl Query {
+    /// Creates a sequence querying a colour
+    #[inline]
+    pub fn new(target: Target) -> Self {
+        Self { target, terminator: Terminator::default() }
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    ///
+    /// Terminals typically reply using the same terminator.
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_command(f, self.target, false)?;
+        write!(f, ";?{}", self.terminator)
+    }
+}
+
+/// Sequence resetting a colour to the terminal’s configured value
+///
+/// Sequences resetting the entire palette are available in the `predefined::palette` mod.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reset {
+    target: Target,
+    terminator: Terminator,
+}
+
+impl Reset {
+    /// Creates a sequence resetting a colour
+    #[inline]
+    pub fn new(target: Target) -> Self {
+        Self { target, terminator: Terminator::default() }
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl fmt::Display for Reset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_command(f, self.target, true)?;
+        f.write_str(self.terminator.as_str())
+    }
+}
+
+/// Parses a reply to a colour query, returning the colour queried and its value
+///
+/// The reply is expected to be the complete sequence, with either terminator.
+///
+/// ```rust
+/// use term_ctrl::palette::{parse_reply, Target};
+///
+/// assert_eq!(Ok((Target::Background, (0x1E, 0x1E, 0x2E))),
+///     parse_reply("\u{1B}]11;rgb:1e1e/1e1e/2e2e\u{1B}\\"));
+/// ```
+pub fn parse_reply(reply: &str) -> Result<(Target, (u8, u8, u8)), Error> {
+    let body = crate::osc::reply_body(reply, OSC_PREFIX).ok_or(Error::NotReply)?;
+    let (target, spec) = match split_param(body).ok_or(Error::NotReply)? {
+        ("4", rest) => {
+            let (index, spec) = split_param(rest).ok_or(Error::NotReply)?;
+            (Target::Index(index.parse().map_err(|_| Error::NotReply)?), spec)
+        },
+        ("10", spec) => (Target::Foreground, spec),
+        ("11", spec) => (Target::Background, spec),
+        _ => return Err(Error::NotReply),
+    };
+    Ok((target, parse_rgb(spec).ok_or(Error::InvalidColour)?))
+}
+
+/// Splits off the first parameter
+fn split_param(s: &str) -> Option<(&str, &str)> {
+    let mut parts = s.splitn(2, ';');
+    Some((parts.next()?, parts.next()?))
+}
+
+/// Parses a colour specification of the form `rgb:R/G/B`
+///
+/// Each component may have from one to four hex digits, and is scaled to eight bits. This is the
+/// form in which terminals report colours.
+pub fn parse_rgb(spec: &str) -> Option<(u8, u8, u8)> {
+    if !spec.starts_with("rgb:") {
+        return None;
+    }
+    let mut components = spec[4..].split('/');
+    let mut next = || components.next().and_then(component);
+    let rgb = (next()?, next()?, next()?);
+    match components.next() {
+        None => Some(rgb),
+        Some(_) => None,
+    }
+}
+
+/// Parses one to four hex digits, scaling the value to eight bits
+fn component(s: &str) -> Option<u8> {
+    if s.is_empty() || s.len() > 4 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(s, 16).ok()?;
+    let max = (1u32 << (4 * s.len())) - 1;
+    Some(((value * 255 + max / 2) / max) as u8)
+}
+
+fn write_command(f: &mut fmt::Formatter, target: Target, reset: bool) -> fmt::Result {
+    let base = match reset {
+        true => 100,
+        false => 0,
+    };
+    match target {
+        Target::Index(n) => write!(f, "{}{};{}", OSC_PREFIX, base + 4, n),
+        Target::Foreground => write!(f, "{}{}", OSC_PREFIX, base + 10),
+        Target::Background => write!(f, "{}{}", OSC_PREFIX, base + 11),
+    }
+}
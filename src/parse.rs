@@ -0,0 +1,319 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Sequence parsing
+//!
+//! This goes the opposite way to the [`seq`](macro@crate::seq) macro, decoding a control sequence
+//! back into the attribute changes it requests.
+//!
+//! ```rust
+//! use term_ctrl::parse::{self, Attribute};
+//! use term_ctrl::colour::Colour;
+//!
+//! let mut attrs = parse::sgr("\u{1B}[38;2;180;15;70;1m").unwrap();
+//! assert_eq!(Some(Ok(Attribute::SetFg(Colour::Rgb(180, 15, 70)))), attrs.next());
+//! assert_eq!(Some(Ok(Attribute::Bold)), attrs.next());
+//! assert_eq!(None, attrs.next());
+//! ```
+//!
+//! Both the semi-colon separated form of the extended colour code sets (`38;5;n`) and the colon
+//! separated form (`38:5:n`, `38:2::r:g:b`) are understood. As per the standard, an empty code is
+//! treated as `0`, so `"\u{1B}[m"` is a reset. The underline style extension (`4:0`-`4:5`) is also
+//! understood.
+
+use core::fmt;
+use core::str::Split;
+use crate::codes::{SEQ_PREFIX, SEQ_POSTFIX};
+use crate::colour::{Colour, BASIC};
+use crate::style::Font;
+
+/// An attribute change requested by a code (or multi-code set) in a control sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    /// Reset everything to defaults (`0`)
+    Reset,
+    /// Bold on (`1`)
+    Bold,
+    /// Dim on (`2`)
+    Dim,
+    /// Italic on (`3`)
+    Italic,
+    /// Underline on (`4`, `4:1`)
+    Underline,
+    /// Curly underline on (`4:3`)
+    CurlyUnderline,
+    /// Dotted underline on (`4:4`)
+    DottedUnderline,
+    /// Dashed underline on (`4:5`)
+    DashedUnderline,
+    /// Blink on (`5`)
+    Blink,
+    /// Rapid-blink on (`6`)
+    RapidBlink,
+    /// Inverse on (`7`)
+    Inverse,
+    /// Invisible on (`8`)
+    Invisible,
+    /// Strike-through on (`9`)
+    Strike,
+    /// Font selection (`10`-`19`)
+    Font(Font),
+    /// Fraktur on (`20`)
+    Fraktur,
+    /// Double-underline on (`21`, `4:2`)
+    DblUnderline,
+    /// Bold and dim off (`22`)
+    ResetIntensity,
+    /// Italic and Fraktur off (`23`)
+    ResetItalic,
+    /// Underline off, of any style (`24`, `4:0`)
+    ResetUnderline,
+    /// Blink off (`25`)
+    ResetBlink,
+    /// Inverse off (`27`)
+    ResetInverse,
+    /// Invisible off (`28`)
+    ResetInvisible,
+    /// Strike-through off (`29`)
+    ResetStrike,
+    /// Text (foreground) colour selection (`30`-`37`, `38;...`, `90`-`97`)
+    SetFg(Colour),
+    /// Reset text (foreground) colour (`39`)
+    ResetFg,
+    /// Background-highlight colour selection (`40`-`47`, `48;...`, `100`-`107`)
+    SetBg(Colour),
+    /// Reset background-highlight colour (`49`)
+    ResetBg,
+    /// Framed on (`51`)
+    Framed,
+    /// Encircled on (`52`)
+    Encircled,
+    /// Overlined on (`53`)
+    Overlined,
+    /// Framed and encircled off (`54`)
+    ResetFramedEncircled,
+    /// Overlined off (`55`)
+    ResetOverlined,
+    /// Underline colour selection (`58;...`)
+    SetUl(Colour),
+    /// Reset underline colour (`59`)
+    ResetUl,
+    /// Ideogram underline or right side line (`60`)
+    IdeogramUnderline,
+    /// Ideogram double underline or double line on the right side (`61`)
+    IdeogramDblUnderline,
+    /// Ideogram overline or left side line (`62`)
+    IdeogramOverline,
+    /// Ideogram double overline or double line on the left side (`63`)
+    IdeogramDblOverline,
+    /// Ideogram stress marking (`64`)
+    IdeogramStressMarking,
+    /// Ideogram attributes off (`65`)
+    ResetIdeogram,
+    /// An underline style not covered by the above (`4:n`), with its number
+    UnknownUnderline(u16),
+    /// A code not covered by the above
+    Unknown(u16),
+}
+
+/// Sequence parsing error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// Not an SGR sequence (lacking the `ESC [` prefix or the `m` postfix)
+    NotSgr,
+    /// A code contained something other than decimal digits
+    InvalidCode,
+    /// A multi-code set (such as an extended colour selection) was cut short
+    Truncated,
+    /// An extended colour selection was of an unknown type, or had a value out of range
+    InvalidColour,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match *self {
+            Error::NotSgr => "not an SGR control sequence",
+            Error::InvalidCode => "invalid code",
+            Error::Truncated => "truncated multi-code set",
+            Error::InvalidColour => "invalid extended colour selection",
+        };
+        f.write_str(desc)
+    }
+}
+
+/// Parses an SGR control sequence
+///
+/// The string given must consist of exactly one complete sequence. An error is returned only if
+/// it does not have the right prefix and postfix; problems with individual codes are reported by
+/// the returned iterator, which stops after the first such error.
+pub fn sgr(seq: &str) -> Result<Attributes<'_>, Error> {
+    if !seq.starts_with(SEQ_PREFIX) || !seq.ends_with(SEQ_POSTFIX)
+        || seq.len() < SEQ_PREFIX.len() + SEQ_POSTFIX.len()
+    {
+        return Err(Error::NotSgr);
+    }
+    Ok(codes(&seq[SEQ_PREFIX.len()..(seq.len() - SEQ_POSTFIX.len())]))
+}
+
+/// Parses a code set
+///
+/// This is like [`sgr()`], but takes just the codes, without the prefix and postfix.
+///
+/// ```rust
+/// use term_ctrl::parse::{self, Attribute};
+/// use term_ctrl::colour::Colour;
+/// use term_ctrl::c256_bg;
+///
+/// let mut attrs = parse::codes(c256_bg!(238));
+/// assert_eq!(Some(Ok(Attribute::SetBg(Colour::Indexed(238)))), attrs.next());
+/// assert_eq!(None, attrs.next());
+/// ```
+pub fn codes(codes: &str) -> Attributes<'_> {
+    Attributes { params: Some(codes.split(';')) }
+}
+
+/// Iterator over the attribute changes of a sequence
+///
+/// See [`sgr()`] and [`codes()`].
+#[derive(Debug, Clone)]
+pub struct Attributes<'a> {
+    /// Remaining parameters, `None` once finished
+    params: Option<Split<'a, char>>,
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = Result<Attribute, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let param = self.params.as_mut()?.next()?;
+        let result = self.decode(param);
+        if result.is_err() {
+            self.params = None;
+        }
+        Some(result)
+    }
+}
+
+impl<'a> Attributes<'a> {
+    /// Decodes a single (semi-colon separated) parameter
+    fn decode(&mut self, param: &str) -> Result<Attribute, Error> {
+        let mut subs = param.split(':');
+        let code = number(subs.next().unwrap_or(""))?;
+        let attr = match code {
+            0 => Attribute::Reset,
+            1 => Attribute::Bold,
+            2 => Attribute::Dim,
+            3 => Attribute::Italic,
+            4 => match subs.next() {
+                None => Attribute::Underline,
+                Some(style) => match number(style)? {
+                    0 => Attribute::ResetUnderline,
+                    1 => Attribute::Underline,
+                    2 => Attribute::DblUnderline,
+                    3 => Attribute::CurlyUnderline,
+                    4 => Attribute::DottedUnderline,
+                    5 => Attribute::DashedUnderline,
+                    n => Attribute::UnknownUnderline(n),
+                },
+            },
+            5 => Attribute::Blink,
+            6 => Attribute::RapidBlink,
+            7 => Attribute::Inverse,
+            8 => Attribute::Invisible,
+            9 => Attribute::Strike,
+            10..=19 => Attribute::Font(FONTS[(code - 10) as usize]),
+            20 => Attribute::Fraktur,
+            21 => Attribute::DblUnderline,
+            22 => Attribute::ResetIntensity,
+            23 => Attribute::ResetItalic,
+            24 => Attribute::ResetUnderline,
+            25 => Attribute::ResetBlink,
+            27 => Attribute::ResetInverse,
+            28 => Attribute::ResetInvisible,
+            29 => Attribute::ResetStrike,
+            30..=37 => Attribute::SetFg(Colour::Basic(BASIC[(code - 30) as usize])),
+            38 => Attribute::SetFg(self.extended(&mut subs, param.contains(':'))?),
+            39 => Attribute::ResetFg,
+            40..=47 => Attribute::SetBg(Colour::Basic(BASIC[(code - 40) as usize])),
+            48 => Attribute::SetBg(self.extended(&mut subs, param.contains(':'))?),
+            49 => Attribute::ResetBg,
+            51 => Attribute::Framed,
+            52 => Attribute::Encircled,
+            53 => Attribute::Overlined,
+            54 => Attribute::ResetFramedEncircled,
+            55 => Attribute::ResetOverlined,
+            58 => Attribute::SetUl(self.extended(&mut subs, param.contains(':'))?),
+            59 => Attribute::ResetUl,
+            60 => Attribute::IdeogramUnderline,
+            61 => Attribute::IdeogramDblUnderline,
+            62 => Attribute::IdeogramOverline,
+            63 => Attribute::IdeogramDblOverline,
+            64 => Attribute::IdeogramStressMarking,
+            65 => Attribute::ResetIdeogram,
+            90..=97 => Attribute::SetFg(Colour::Bright(BASIC[(code - 90) as usize])),
+            100..=107 => Attribute::SetBg(Colour::Bright(BASIC[(code - 100) as usize])),
+            _ => Attribute::Unknown(code),
+        };
+        Ok(attr)
+    }
+
+    /// Decodes the rest of an extended colour selection
+    ///
+    /// With the colon form, the values come from the remaining sub-parameters of the same
+    /// parameter, otherwise they come from the following parameters.
+    fn extended(&mut self, subs: &mut Split<'_, char>, colon_form: bool) -> Result<Colour, Error> {
+        match self.value(subs, colon_form)? {
+            5 => Ok(Colour::Indexed(self.value(subs, colon_form)?)),
+            2 => {
+                let mut rgb = [0u8; 3];
+                for c in rgb.iter_mut() {
+                    *c = self.value(subs, colon_form)?;
+                }
+                // The colon form may include a colour-space ID before the components
+                if colon_form {
+                    if let Some(last) = subs.next() {
+                        rgb = [rgb[1], rgb[2], component(number(last)?)?];
+                    }
+                }
+                Ok(Colour::Rgb(rgb[0], rgb[1], rgb[2]))
+            },
+            _ => Err(Error::InvalidColour),
+        }
+    }
+
+    /// Takes the next value of an extended colour selection
+    fn value(&mut self, subs: &mut Split<'_, char>, colon_form: bool) -> Result<u8, Error> {
+        let value = match colon_form {
+            true => subs.next(),
+            false => self.params.as_mut().and_then(|p| p.next()),
+        };
+        component(number(value.ok_or(Error::Truncated)?)?)
+    }
+}
+
+const FONTS: [Font; 10] = [
+    Font::Default, Font::Alt1, Font::Alt2, Font::Alt3, Font::Alt4,
+    Font::Alt5, Font::Alt6, Font::Alt7, Font::Alt8, Font::Alt9,
+];
+
+/// Parses a decimal code, with an empty string treated as zero
+fn number(s: &str) -> Result<u16, Error> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidCode);
+    }
+    // Saturate rather than overflow; anything this large is meaningless anyway
+    Ok(s.bytes().fold(0u16, |n, b| n.saturating_mul(10).saturating_add(u16::from(b - b'0'))))
+}
+
+/// Checks that a number is within the range of a colour component (or palette index)
+fn component(n: u16) -> Result<u8, Error> {
+    match n {
+        0..=255 => Ok(n as u8),
+        _ => Err(Error::InvalidColour),
+    }
+}
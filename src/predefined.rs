@@ -48,6 +48,17 @@ pub mod effects {
     /// Double-underline
     pub const DBL_UNDERLINE: &str = seq!(21);
 
+    /// Curly (wavy) underline
+    ///
+    /// This and the other underline styles below are [extensions].
+    ///
+    /// [extensions]: crate#underline-styles-and-colour
+    pub const CURLY_UNDERLINE:  &str = seq!("4:3");
+    /// Dotted underline
+    pub const DOTTED_UNDERLINE: &str = seq!("4:4");
+    /// Dashed underline
+    pub const DASHED_UNDERLINE: &str = seq!("4:5");
+
     /// Alias for removing blink
     pub const STEADY:    &str = remove::BLINK;
     /// Alias for removing inverse
@@ -116,6 +127,13 @@ pub mod colours {
     pub const RESET_FG: &str = fg::RESET;
     /// Alias for resetting background colour
     pub const RESET_BG: &str = bg::RESET;
+    /// Reset underline colour
+    ///
+    /// There are no predefined constants for setting an [underline colour]; use the
+    /// [`c256_ul`](crate::c256_ul) and [`rgb_ul`](crate::rgb_ul) macros.
+    ///
+    /// [underline colour]: crate#underline-styles-and-colour
+    pub const RESET_UL: &str = seq!(59);
 
     /// Text (foreground) colour
     pub mod fg {
@@ -238,3 +256,138 @@ pub mod combinations {
         pub const WHITE:   &str = seq!(37,1);
     }
 }
+
+/// Cursor positioning
+///
+/// See also the [`cursor`](mod@crate::cursor) mod, and macros such as
+/// [`cursor_up`](macro@crate::cursor_up), for movement sequences.
+pub mod cursor {
+    /// Save cursor position (DECSC)
+    ///
+    /// This also saves the current formatting, along with some other state.
+    pub const SAVE:            &str = "\u{1B}7";
+    /// Restore cursor position (DECRC)
+    pub const RESTORE:         &str = "\u{1B}8";
+    /// Save cursor position (SCOSC)
+    ///
+    /// This alternative form saves just the position. Note that it is not supported by all
+    /// terminals.
+    pub const SAVE_SCO:        &str = "\u{1B}[s";
+    /// Restore cursor position (SCORC)
+    pub const RESTORE_SCO:     &str = "\u{1B}[u";
+    /// Move cursor to the top-left corner
+    pub const HOME:            &str = "\u{1B}[H";
+    /// Cursor position report query (CPR)
+    ///
+    /// The terminal replies with the cursor position, which can be parsed with
+    /// [`cursor::parse_position_report()`](crate::cursor::parse_position_report).
+    pub const REPORT_POSITION: &str = "\u{1B}[6n";
+}
+
+/// Erasing
+///
+/// Note that erasing does not move the cursor.
+pub mod erase {
+    /// Erase the entire screen
+    pub const SCREEN:       &str = "\u{1B}[2J";
+    /// Erase from the cursor to the end of the screen
+    pub const SCREEN_BELOW: &str = "\u{1B}[0J";
+    /// Erase from the start of the screen to the cursor
+    pub const SCREEN_ABOVE: &str = "\u{1B}[1J";
+    /// Erase the scrollback buffer (saved lines)
+    pub const SCROLLBACK:   &str = "\u{1B}[3J";
+
+    /// Erase the entire line
+    pub const LINE:         &str = "\u{1B}[2K";
+    /// Erase from the cursor to the end of the line
+    pub const LINE_RIGHT:   &str = "\u{1B}[0K";
+    /// Erase from the start of the line to the cursor
+    pub const LINE_LEFT:    &str = "\u{1B}[1K";
+}
+
+/// DEC private modes
+///
+/// Each of these sequences sets (enables) a mode; the matching sequences that reset (disable) them
+/// are in the `reset` sub-mod. See also the [`modes`](mod@crate::modes) mod.
+pub mod modes {
+    /// Alternate screen buffer (also saving the cursor and clearing the alternate screen)
+    pub const ALT_SCREEN:      &str = "\u{1B}[?1049h";
+    /// Cursor visibility
+    pub const CURSOR_VISIBLE:  &str = "\u{1B}[?25h";
+    /// Auto-wrap (wrapping text at the right edge to the next line)
+    pub const AUTO_WRAP:       &str = "\u{1B}[?7h";
+    /// Bracketed paste (pasted text is wrapped in special sequences)
+    pub const BRACKETED_PASTE: &str = "\u{1B}[?2004h";
+    /// Focus reporting (focus in and out is reported by the terminal)
+    pub const FOCUS_REPORTING: &str = "\u{1B}[?1004h";
+    /// Synchronised output (the display is not updated until this is reset)
+    pub const SYNC_OUTPUT:     &str = "\u{1B}[?2026h";
+    /// Mouse click reporting (button press and release)
+    pub const MOUSE_CLICKS:    &str = "\u{1B}[?1000h";
+    /// Mouse drag reporting (as for clicks, plus motion while a button is held)
+    pub const MOUSE_DRAG:      &str = "\u{1B}[?1002h";
+    /// Mouse motion reporting (as for clicks, plus all motion)
+    pub const MOUSE_MOTION:    &str = "\u{1B}[?1003h";
+    /// SGR extended mouse reporting format (used in combination with one of the above)
+    pub const MOUSE_SGR:       &str = "\u{1B}[?1006h";
+
+    /// Alias for showing the cursor
+    pub const SHOW_CURSOR: &str = CURSOR_VISIBLE;
+    /// Alias for hiding the cursor
+    pub const HIDE_CURSOR: &str = reset::CURSOR_VISIBLE;
+
+    /// Sequences that reset specific modes
+    pub mod reset {
+        /// Alternate screen buffer (returning to the normal screen and restoring the cursor)
+        pub const ALT_SCREEN:      &str = "\u{1B}[?1049l";
+        /// Cursor visibility
+        pub const CURSOR_VISIBLE:  &str = "\u{1B}[?25l";
+        /// Auto-wrap
+        pub const AUTO_WRAP:       &str = "\u{1B}[?7l";
+        /// Bracketed paste
+        pub const BRACKETED_PASTE: &str = "\u{1B}[?2004l";
+        /// Focus reporting
+        pub const FOCUS_REPORTING: &str = "\u{1B}[?1004l";
+        /// Synchronised output
+        pub const SYNC_OUTPUT:     &str = "\u{1B}[?2026l";
+        /// Mouse click reporting
+        pub const MOUSE_CLICKS:    &str = "\u{1B}[?1000l";
+        /// Mouse drag reporting
+        pub const MOUSE_DRAG:      &str = "\u{1B}[?1002l";
+        /// Mouse motion reporting
+        pub const MOUSE_MOTION:    &str = "\u{1B}[?1003l";
+        /// SGR extended mouse reporting format
+        pub const MOUSE_SGR:       &str = "\u{1B}[?1006l";
+    }
+}
+
+/// Window and tab title stack
+///
+/// See also the [`title`](mod@crate::title) mod.
+pub mod title {
+    /// Saves the icon name and window title to the stack
+    pub const PUSH:        &str = "\u{1B}[22;0t";
+    /// Restores the icon name and window title from the stack
+    pub const POP:         &str = "\u{1B}[23;0t";
+    /// Saves just the icon name to the stack
+    pub const PUSH_ICON:   &str = "\u{1B}[22;1t";
+    /// Restores just the icon name from the stack
+    pub const POP_ICON:    &str = "\u{1B}[23;1t";
+    /// Saves just the window title to the stack
+    pub const PUSH_WINDOW: &str = "\u{1B}[22;2t";
+    /// Restores just the window title from the stack
+    pub const POP_WINDOW:  &str = "\u{1B}[23;2t";
+}
+
+/// Palette and default colour resets
+///
+/// These reset colours to the terminal’s configured values. See also the
+/// [`palette`](mod@crate::palette) mod.
+pub mod palette {
+    /// Resets the entire 256 colour palette
+    pub const RESET_PALETTE:    &str = "\u{1B}]104\u{1B}\\";
+    /// Resets the default text colour
+    pub const RESET_FOREGROUND: &str = "\u{1B}]110\u{1B}\\";
+    /// Resets the default background colour
+    pub const RESET_BACKGROUND: &str = "\u{1B}]111\u{1B}\\";
+}
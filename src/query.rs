@@ -0,0 +1,212 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Terminal capability queries
+//!
+//! Rather than guessing at what a terminal supports from the `TERM` environment variable, a
+//! program can ask the terminal itself. This mod provides the sequences for the common queries,
+//! along with functions for parsing the replies:
+//!
+//!  - Primary device attributes ([`DA1`]), giving the terminal’s conformance level and features.
+//!  - Secondary device attributes ([`DA2`]), giving a terminal type id and version.
+//!  - Terminal name and version ([`XTVERSION`]).
+//!  - Mode state ([`RequestMode`]), giving whether a mode is supported, and if so whether set.
+//!
+//! ```rust
+//! use term_ctrl::query::{parse_mode_reply, ModeState, RequestMode};
+//! use term_ctrl::modes::Mode;
+//!
+//! assert_eq!("\u{1B}[?2026$p", format!("{}", RequestMode::from(Mode::SyncOutput)));
+//! // Having sent that and read the reply...
+//! let reply = "\u{1B}[?2026;2$y";
+//! assert_eq!(Some((2026, ModeState::Reset)), parse_mode_reply(reply));
+//! ```
+//!
+//! Note that terminals simply ignore queries they do not understand, so a reply may never come.
+//! Since virtually all terminals reply to DA1, the usual approach is to send it after the query of
+//! interest; if its reply arrives first, the other query is not supported. The
+//! [`support::round_trip()`] helper does exactly this, keeping apart any other input read along the
+//! way.
+//!
+//! [`support::round_trip()`]: crate::support::round_trip
+
+use core::convert::TryFrom;
+use core::fmt;
+use crate::modes::Mode;
+
+/// Primary device attributes (DA1) query
+pub const DA1: &str = "\u{1B}[c";
+/// Secondary device attributes (DA2) query
+pub const DA2: &str = "\u{1B}[>c";
+/// Terminal name and version (XTVERSION) query
+pub const XTVERSION: &str = "\u{1B}[>0q";
+
+/// Primary device attributes, as replied to a [`DA1`] query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrimaryAttributes {
+    /// Conformance level (e.g. `62` for VT220, or `1` for a VT100)
+    pub level: u16,
+    /// Feature codes reported, as a set of bits (codes of 64 and above are ignored)
+    pub features: u64,
+}
+
+impl PrimaryAttributes {
+    /// Is a feature reported?
+    ///
+    /// Common ones include `4` (sixel graphics), `22` (ANSI colour) and `52` (clipboard access).
+    #[inline]
+    pub fn has_feature(&self, code: u16) -> bool {
+        code < 64 && self.features & (1 << code) != 0
+    }
+}
+
+/// Secondary device attributes, as replied to a [`DA2`] query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SecondaryAttributes {
+    /// Terminal type id (e.g. `41` for xterm, or `1` for many others)
+    pub terminal: u16,
+    /// Firmware version (e.g. the patch number for xterm)
+    pub version: u32,
+    /// ROM cartridge registration number (usually zero)
+    pub rom: u16,
+}
+
+/// State of a mode, as replied to a [`RequestMode`] query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModeState {
+    /// The mode is not recognised (not supported)
+    NotRecognised,
+    Set,
+    Reset,
+    /// The mode is set, and cannot be changed
+    PermanentlySet,
+    /// The mode is reset, and cannot be changed
+    PermanentlyReset,
+}
+
+impl ModeState {
+    /// Is the mode supported (recognised)?
+    #[inline]
+    pub fn is_supported(self) -> bool {
+        self != ModeState::NotRecognised
+    }
+
+    /// Is the mode set (either changeably or permanently)?
+    #[inline]
+    pub fn is_set(self) -> bool {
+        self == ModeState::Set || self == ModeState::PermanentlySet
+    }
+}
+
+/// DEC private mode state query (DECRQM)
+///
+/// The terminal replies with the state of the mode, which [`parse_mode_reply()`] can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequestMode(pub u16);
+
+impl From<Mode> for RequestMode {
+    #[inline]
+    fn from(mode: Mode) -> Self {
+        RequestMode(mode.code())
+    }
+}
+
+impl fmt::Display for RequestMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\u{1B}[?{}$p", self.0)
+    }
+}
+
+/// Parses a reply to a [`DA1`] query
+///
+/// The reply is expected to be the complete sequence, `CSI ? level ; features... c`.
+pub fn parse_da1_reply(reply: &str) -> Option<PrimaryAttributes> {
+    let mut params = numbers(between(reply, "\u{1B}[?", "c")?)?;
+    let level = params.next()??;
+    let mut features = 0u64;
+    for code in params {
+        let code = code?;
+        if code < 64 {
+            features |= 1 << code;
+        }
+    }
+    Some(PrimaryAttributes { level: u16::try_from(level).ok()?, features })
+}
+
+/// Parses a reply to a [`DA2`] query
+///
+/// The reply is expected to be the complete sequence, `CSI > terminal ; version ; rom c`, though
+/// the ROM number is optional.
+pub fn parse_da2_reply(reply: &str) -> Option<SecondaryAttributes> {
+    let mut params = numbers(between(reply, "\u{1B}[>", "c")?)?;
+    let terminal = u16::try_from(params.next()??).ok()?;
+    let version = params.next()??;
+    let rom = match params.next() {
+        Some(rom) => u16::try_from(rom?).ok()?,
+        None => 0,
+    };
+    match params.next() {
+        None => Some(SecondaryAttributes { terminal, version, rom }),
+        Some(_) => None,
+    }
+}
+
+/// Parses a reply to an [`XTVERSION`] query, giving the terminal name and version text
+///
+/// The reply is expected to be the complete sequence, `DCS > | text ST`, and the text typically
+/// takes a form such as `XTerm(388)` or `kitty(0.31.0)`.
+pub fn parse_xtversion_reply(reply: &str) -> Option<&str> {
+    let text = crate::osc::reply_body(reply, "\u{1B}P>|")?;
+    match text.chars().any(char::is_control) {
+        true => None,
+        false => Some(text),
+    }
+}
+
+/// Parses a reply to a [`RequestMode`] query, giving the mode number and its state
+///
+/// The reply is expected to be the complete sequence, `CSI ? mode ; state $ y`.
+pub fn parse_mode_reply(reply: &str) -> Option<(u16, ModeState)> {
+    let mut params = numbers(between(reply, "\u{1B}[?", "$y")?)?;
+    let mode = u16::try_from(params.next()??).ok()?;
+    let state = match params.next()?? {
+        0 => ModeState::NotRecognised,
+        1 => ModeState::Set,
+        2 => ModeState::Reset,
+        3 => ModeState::PermanentlySet,
+        4 => ModeState::PermanentlyReset,
+        _ => return None,
+    };
+    match params.next() {
+        None => Some((mode, state)),
+        Some(_) => None,
+    }
+}
+
+/// Gets the part of a reply between the given prefix and suffix
+fn between<'a>(reply: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    match reply.len() >= prefix.len() + suffix.len()
+        && reply.starts_with(prefix) && reply.ends_with(suffix)
+    {
+        true => Some(&reply[prefix.len()..(reply.len() - suffix.len())]),
+        false => None,
+    }
+}
+
+/// Splits semi-colon separated numeric parameters, each given as `None` if invalid
+///
+/// Gives `None` if there are no parameters.
+fn numbers(params: &str) -> Option<impl Iterator<Item = Option<u32>> + '_> {
+    match params.is_empty() {
+        true => None,
+        false => Some(params.split(';').map(|p| match p.bytes().all(|b| b.is_ascii_digit()) {
+            true => p.parse().ok(),
+            false => None,
+        })),
+    }
+}
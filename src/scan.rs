@@ -0,0 +1,130 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Escape sequence scanning
+//!
+//! A byte-at-a-time state machine for telling apart text from escape sequences, following the
+//! ECMA-48 syntax. It is only concerned with where sequences begin and end, not what they mean.
+//!
+//! Since all of the bytes that start, end or change the state of a sequence are ASCII, and anything
+//! unexpected within a sequence (other than within a string type sequence, which consumes
+//! everything up to its terminator) aborts it, removing the bytes that are not text from valid
+//! UTF-8 always leaves valid UTF-8.
+
+const ESC: u8 = 0x1B;
+const BEL: u8 = 0x07;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1A;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Normal text
+    Ground,
+    /// After `ESC`
+    Escape,
+    /// After `ESC` and one or more intermediate bytes
+    EscapeIntermediate,
+    /// Within a control sequence (`ESC [`)
+    Csi,
+    /// Within an operating system command (`ESC ]`), terminated by `BEL` or `ST`
+    Osc,
+    /// Within another string type sequence (`DCS`, `SOS`, `PM` or `APC`), terminated by `ST`
+    String,
+    /// After `ESC` within a string type sequence, possibly the start of `ST` (`ESC \`)
+    StringEscape,
+}
+
+/// Escape sequence scanner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Scanner {
+    state: State,
+}
+
+impl Default for Scanner {
+    #[inline]
+    fn default() -> Self {
+        Scanner { state: State::Ground }
+    }
+}
+
+impl Scanner {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next byte, returning `true` if it is text rather than part of a sequence
+    pub fn feed(&mut self, b: u8) -> bool {
+        match self.state {
+            State::Ground => match b {
+                ESC => self.state = State::Escape,
+                _ => return true,
+            },
+            State::Escape | State::StringEscape => match b {
+                b'\\' if self.state == State::StringEscape => self.state = State::Ground,
+                b'[' => self.state = State::Csi,
+                b']' => self.state = State::Osc,
+                b'P' | b'X' | b'^' | b'_' => self.state = State::String,
+                0x20..=0x2F => self.state = State::EscapeIntermediate,
+                0x30..=0x7E => self.state = State::Ground,
+                ESC => self.state = State::Escape,
+                CAN | SUB => self.state = State::Ground,
+                _ => return self.abort(b),
+            },
+            State::EscapeIntermediate => match b {
+                0x20..=0x2F => {},
+                0x30..=0x7E => self.state = State::Ground,
+                ESC => self.state = State::Escape,
+                CAN | SUB => self.state = State::Ground,
+                _ => return self.abort(b),
+            },
+            State::Csi => match b {
+                0x20..=0x3F => {},
+                0x40..=0x7E => self.state = State::Ground,
+                ESC => self.state = State::Escape,
+                CAN | SUB => self.state = State::Ground,
+                _ => return self.abort(b),
+            },
+            State::Osc => match b {
+                BEL | CAN | SUB => self.state = State::Ground,
+                ESC => self.state = State::StringEscape,
+                _ => {},
+            },
+            State::String => match b {
+                CAN | SUB => self.state = State::Ground,
+                ESC => self.state = State::StringEscape,
+                _ => {},
+            },
+        }
+        false
+    }
+
+    /// Abandons a malformed sequence, treating the unexpected byte as text
+    #[inline]
+    fn abort(&mut self, b: u8) -> bool {
+        self.state = State::Ground;
+        self.feed(b)
+    }
+}
+
+/// Finds where the escape sequence that `buf` ends with starts, if it does end with one
+#[cfg(all(unix, feature = "std"))]
+pub(crate) fn last_sequence_start(buf: &[u8]) -> Option<usize> {
+    let mut scanner = Scanner::new();
+    let mut start = 0;
+    let mut last = None;
+    for (i, &b) in buf.iter().enumerate() {
+        let text = scanner.feed(b);
+        // An `ESC` outside of a string type sequence starts a new sequence
+        if scanner.state == State::Escape {
+            start = i;
+        }
+        last = if !text && scanner.state == State::Ground { Some(start) } else { None };
+    }
+    last
+}
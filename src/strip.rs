@@ -0,0 +1,63 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Sequence stripping
+//!
+//! Helpers for removing control sequences from text, for instance where text already containing
+//! sequences is to be output to something other than a terminal.
+//!
+//! All escape sequences are removed, not just the formatting ones, this including control
+//! sequences (`ESC [`), operating system commands (`ESC ]`) and other string type sequences, and
+//! two byte escapes such as `ESC 7`. Any incomplete sequence at the end of the input is removed
+//! also.
+//!
+//! Text that contains no sequences is passed back as is, without allocating.
+//!
+//! This requires the `alloc` feature.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::scan::Scanner;
+
+/// Removes escape sequences from a string
+///
+/// ```rust
+/// use term_ctrl::strip::strip;
+/// use term_ctrl::predefined::{RESET, colours::fg::RED};
+///
+/// let text = format!("{}Error:{} You made an error!", RED, RESET);
+/// assert_eq!("Error: You made an error!", strip(&text));
+/// ```
+pub fn strip(text: &str) -> Cow<'_, str> {
+    match strip_bytes(text.as_bytes()) {
+        Cow::Borrowed(_) => Cow::Borrowed(text),
+        // Stripping never splits a multi-byte char, so this should always succeed
+        Cow::Owned(bytes) => Cow::Owned(String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())),
+    }
+}
+
+/// Removes escape sequences from a byte buffer
+///
+/// ```rust
+/// use term_ctrl::strip::strip_bytes;
+///
+/// assert_eq!(&b"Hello world!"[..], &*strip_bytes(b"Hello \x1B[1mworld\x1B[0m!"));
+/// ```
+pub fn strip_bytes(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let start = match bytes.iter().position(|&b| b == 0x1B) {
+        Some(pos) => pos,
+        None => return Cow::Borrowed(bytes),
+    };
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..start]);
+    let mut scanner = Scanner::new();
+    out.extend(bytes[start..].iter().filter(|&&b| scanner.feed(b)));
+    Cow::Owned(out)
+}
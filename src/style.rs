@@ -0,0 +1,470 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Typed styles
+//!
+//! The [`predefined`](mod@crate::predefined) sequences are fixed strings, which is fine for a lot
+//! of uses but not if you want to hold a style as a value, alter it, or build it from some
+//! configuration at runtime. The [`Style`] type here fills that gap; it holds a selection of
+//! colours and effects and, when formatted with [`Display`](core::fmt::Display), writes out the
+//! minimal control sequence that applies them.
+//!
+//! ```rust
+//! use term_ctrl::style::{Style, Effects};
+//! use term_ctrl::colour::{Colour, BasicColour};
+//!
+//! let style = Style::new().fg(Colour::Basic(BasicColour::Red)).bold();
+//! assert_eq!("\u{1B}[1;31m", format!("{}", style));
+//!
+//! let style = style.bg(Colour::Bright(BasicColour::Blue)).effects(Effects::UNDERLINE);
+//! assert_eq!("\u{1B}[1;4;31;104m", format!("{}", style));
+//! ```
+//!
+//! Note that as with the predefined sequences, a style only *applies* formatting; remember to
+//! follow the formatted text with a reset.
+
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+use crate::colour::{Colour, ColourLevel};
+use crate::parse::Attribute;
+
+/// A set of effects
+///
+/// This covers the on/off effects of the `effects` and `misc` predefined groups. Sets can be
+/// combined with the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Effects(u32);
+
+impl Effects {
+    /// No effects
+    pub const NONE:                    Effects = Effects(0);
+    /// Bold (increase intensity)
+    pub const BOLD:                    Effects = Effects(1 << 0);
+    /// Dim (aka faint; decrease intensity)
+    pub const DIM:                     Effects = Effects(1 << 1);
+    /// Italic
+    pub const ITALIC:                  Effects = Effects(1 << 2);
+    /// Underline
+    pub const UNDERLINE:               Effects = Effects(1 << 3);
+    /// Blink
+    pub const BLINK:                   Effects = Effects(1 << 4);
+    /// Rapid-blink
+    pub const RAPID_BLINK:             Effects = Effects(1 << 5);
+    /// Inverse (swap foreground/background colours)
+    pub const INVERSE:                 Effects = Effects(1 << 6);
+    /// Invisible (hidden)
+    pub const INVISIBLE:               Effects = Effects(1 << 7);
+    /// Strike-through
+    pub const STRIKE:                  Effects = Effects(1 << 8);
+    /// Fraktur
+    pub const FRAKTUR:                 Effects = Effects(1 << 9);
+    /// Double-underline
+    pub const DBL_UNDERLINE:           Effects = Effects(1 << 10);
+    /// Framed
+    pub const FRAMED:                  Effects = Effects(1 << 11);
+    /// Encircled
+    pub const ENCIRCLED:               Effects = Effects(1 << 12);
+    /// Overlined
+    pub const OVERLINED:               Effects = Effects(1 << 13);
+    /// Ideogram underline or right side line
+    pub const IDEOGRAM_UNDERLINE:      Effects = Effects(1 << 14);
+    /// Ideogram double underline or double line on the right side
+    pub const IDEOGRAM_DBL_UNDERLINE:  Effects = Effects(1 << 15);
+    /// Ideogram overline or left side line
+    pub const IDEOGRAM_OVERLINE:       Effects = Effects(1 << 16);
+    /// Ideogram double overline or double line on the left side
+    pub const IDEOGRAM_DBL_OVERLINE:   Effects = Effects(1 << 17);
+    /// Ideogram stress marking
+    pub const IDEOGRAM_STRESS_MARKING: Effects = Effects(1 << 18);
+    /// Curly (wavy) underline
+    pub const CURLY_UNDERLINE:         Effects = Effects(1 << 19);
+    /// Dotted underline
+    pub const DOTTED_UNDERLINE:        Effects = Effects(1 << 20);
+    /// Dashed underline
+    pub const DASHED_UNDERLINE:        Effects = Effects(1 << 21);
+
+    /// All of the ideogram effects
+    pub const IDEOGRAM: Effects = Effects(0b11111 << 14);
+    /// All of the underline styles
+    pub const UNDERLINES: Effects = Effects(1 << 3 | 1 << 10 | 0b111 << 19);
+
+    /// Is the set empty?
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Does this set contain all of the effects in `other`?
+    #[inline]
+    pub fn contains(self, other: Effects) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds the effects in `other` to this set
+    #[inline]
+    pub fn insert(&mut self, other: Effects) {
+        self.0 |= other.0;
+    }
+
+    /// Removes the effects in `other` from this set
+    #[inline]
+    pub fn remove(&mut self, other: Effects) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for Effects {
+    type Output = Effects;
+
+    #[inline]
+    fn bitor(self, rhs: Effects) -> Effects {
+        Effects(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Effects {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Effects) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Code for each effect, in code order
+const EFFECT_CODES: [(Effects, &str); 22] = [
+    (Effects::BOLD,                    "1"),
+    (Effects::DIM,                     "2"),
+    (Effects::ITALIC,                  "3"),
+    (Effects::UNDERLINE,               "4"),
+    (Effects::CURLY_UNDERLINE,         "4:3"),
+    (Effects::DOTTED_UNDERLINE,        "4:4"),
+    (Effects::DASHED_UNDERLINE,        "4:5"),
+    (Effects::BLINK,                   "5"),
+    (Effects::RAPID_BLINK,             "6"),
+    (Effects::INVERSE,                 "7"),
+    (Effects::INVISIBLE,               "8"),
+    (Effects::STRIKE,                  "9"),
+    (Effects::FRAKTUR,                 "20"),
+    (Effects::DBL_UNDERLINE,           "21"),
+    (Effects::FRAMED,                  "51"),
+    (Effects::ENCIRCLED,               "52"),
+    (Effects::OVERLINED,               "53"),
+    (Effects::IDEOGRAM_UNDERLINE,      "60"),
+    (Effects::IDEOGRAM_DBL_UNDERLINE,  "61"),
+    (Effects::IDEOGRAM_OVERLINE,       "62"),
+    (Effects::IDEOGRAM_DBL_OVERLINE,   "63"),
+    (Effects::IDEOGRAM_STRESS_MARKING, "64"),
+];
+
+/// Font selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Font {
+    /// The primary (default) font
+    Default = 0,
+    Alt1 = 1,
+    Alt2 = 2,
+    Alt3 = 3,
+    Alt4 = 4,
+    Alt5 = 5,
+    Alt6 = 6,
+    Alt7 = 7,
+    Alt8 = 8,
+    Alt9 = 9,
+}
+
+/// A combination of colours and effects
+///
+/// An unset (`None`) colour or font leaves whatever is currently in effect unchanged, it does not
+/// reset it. Use [`Colour::Default`] to explicitly select the default colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    /// Text (foreground) colour
+    pub fg: Option<Colour>,
+    /// Background-highlight colour
+    pub bg: Option<Colour>,
+    /// [Underline colour](crate#underline-styles-and-colour)
+    pub ul: Option<Colour>,
+    /// Effects
+    pub effects: Effects,
+    /// Font selection
+    pub font: Option<Font>,
+}
+
+impl Style {
+    /// Creates a new, empty, style
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text (foreground) colour
+    #[inline]
+    pub fn fg<C: Into<Colour>>(mut self, colour: C) -> Self {
+        self.fg = Some(colour.into());
+        self
+    }
+
+    /// Sets the background-highlight colour
+    #[inline]
+    pub fn bg<C: Into<Colour>>(mut self, colour: C) -> Self {
+        self.bg = Some(colour.into());
+        self
+    }
+
+    /// Sets the underline colour
+    ///
+    /// This only shows with one of the underline effects, and only where the terminal supports
+    /// [underline colour](crate#underline-styles-and-colour).
+    ///
+    /// ```rust
+    /// use term_ctrl::style::{Style, Effects};
+    /// use term_ctrl::colour::{Colour, BasicColour};
+    ///
+    /// let style = Style::new().effects(Effects::CURLY_UNDERLINE).ul(BasicColour::Red);
+    /// assert_eq!("\u{1B}[4:3;58;5;1m", format!("{}", style));
+    /// ```
+    #[inline]
+    pub fn ul<C: Into<Colour>>(mut self, colour: C) -> Self {
+        self.ul = Some(colour.into());
+        self
+    }
+
+    /// Adds a set of effects
+    ///
+    /// Underline styles replace one another, as on the terminal, so an underline style given
+    /// replaces any the style already has. Where several are given, just one is used, taking
+    /// dashed, dotted, curly, double and plain underline in that order of precedence.
+    pub fn effects(mut self, effects: Effects) -> Self {
+        let underlines = effects.0 & Effects::UNDERLINES.0;
+        if underlines != 0 {
+            // The underline styles are in order of precedence, so that of the highest bit wins
+            self.set_underline(Effects(1 << (31 - underlines.leading_zeros())));
+        }
+        self.effects.insert(Effects(effects.0 & !Effects::UNDERLINES.0));
+        self
+    }
+
+    /// Sets the font
+    #[inline]
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Adds bold
+    #[inline]
+    pub fn bold(self) -> Self {
+        self.effects(Effects::BOLD)
+    }
+
+    /// Adds dim
+    #[inline]
+    pub fn dim(self) -> Self {
+        self.effects(Effects::DIM)
+    }
+
+    /// Adds italic
+    #[inline]
+    pub fn italic(self) -> Self {
+        self.effects(Effects::ITALIC)
+    }
+
+    /// Adds underline (replacing any other underline style)
+    #[inline]
+    pub fn underline(self) -> Self {
+        self.effects(Effects::UNDERLINE)
+    }
+
+    /// Adds blink
+    #[inline]
+    pub fn blink(self) -> Self {
+        self.effects(Effects::BLINK)
+    }
+
+    /// Adds inverse
+    #[inline]
+    pub fn inverse(self) -> Self {
+        self.effects(Effects::INVERSE)
+    }
+
+    /// Adds invisible
+    #[inline]
+    pub fn invisible(self) -> Self {
+        self.effects(Effects::INVISIBLE)
+    }
+
+    /// Adds strike-through
+    #[inline]
+    pub fn strike(self) -> Self {
+        self.effects(Effects::STRIKE)
+    }
+
+    /// Downgrades the colours of the style to those supported at the given level
+    ///
+    /// With [`ColourLevel::None`] this gives a plain style, since no sequences at all should be
+    /// used at that level.
+    ///
+    /// ```rust
+    /// use term_ctrl::style::Style;
+    /// use term_ctrl::colour::{Colour, ColourLevel};
+    ///
+    /// let style = Style::new().fg(Colour::Rgb(180, 15, 70)).bold();
+    /// assert_eq!("\u{1B}[1;38;5;125m", format!("{}", style.downgrade(ColourLevel::Ansi256)));
+    /// assert_eq!("", format!("{}", style.downgrade(ColourLevel::None)));
+    /// ```
+    pub fn downgrade(&self, level: ColourLevel) -> Style {
+        if level == ColourLevel::None {
+            return Style::default();
+        }
+        Style {
+            fg: self.fg.and_then(|c| c.downgrade(level)),
+            bg: self.bg.and_then(|c| c.downgrade(level)),
+            ul: self.ul.and_then(|c| c.downgrade(level)),
+            ..*self
+        }
+    }
+
+    /// Applies an attribute change to the style
+    ///
+    /// This treats the style as the cumulative state reached from a reset, as when tracking the
+    /// effect of a run of sequences (see the [`parse`](mod@crate::parse) mod). Resets thus clear
+    /// the relevant parts of the style, and unknown codes are ignored.
+    ///
+    /// ```rust
+    /// use term_ctrl::style::Style;
+    /// use term_ctrl::colour::{Colour, BasicColour};
+    /// use term_ctrl::parse;
+    ///
+    /// let mut style = Style::new();
+    /// for seq in &["\u{1B}[1;3;31m", "\u{1B}[23m"] {
+    ///     for attr in parse::sgr(seq).unwrap() {
+    ///         style.apply(attr.unwrap());
+    ///     }
+    /// }
+    /// assert_eq!(Style::new().fg(Colour::Basic(BasicColour::Red)).bold(), style);
+    /// ```
+    pub fn apply(&mut self, attr: Attribute) {
+        match attr {
+            Attribute::Reset => *self = Self::default(),
+            Attribute::Bold => self.effects.insert(Effects::BOLD),
+            Attribute::Dim => self.effects.insert(Effects::DIM),
+            Attribute::Italic => self.effects.insert(Effects::ITALIC),
+            Attribute::Underline => self.set_underline(Effects::UNDERLINE),
+            Attribute::CurlyUnderline => self.set_underline(Effects::CURLY_UNDERLINE),
+            Attribute::DottedUnderline => self.set_underline(Effects::DOTTED_UNDERLINE),
+            Attribute::DashedUnderline => self.set_underline(Effects::DASHED_UNDERLINE),
+            Attribute::Blink => self.effects.insert(Effects::BLINK),
+            Attribute::RapidBlink => self.effects.insert(Effects::RAPID_BLINK),
+            Attribute::Inverse => self.effects.insert(Effects::INVERSE),
+            Attribute::Invisible => self.effects.insert(Effects::INVISIBLE),
+            Attribute::Strike => self.effects.insert(Effects::STRIKE),
+            Attribute::Font(Font::Default) => self.font = None,
+            Attribute::Font(font) => self.font = Some(font),
+            Attribute::Fraktur => self.effects.insert(Effects::FRAKTUR),
+            Attribute::DblUnderline => self.set_underline(Effects::DBL_UNDERLINE),
+            Attribute::ResetIntensity => self.effects.remove(Effects::BOLD | Effects::DIM),
+            Attribute::ResetItalic => self.effects.remove(Effects::ITALIC | Effects::FRAKTUR),
+            Attribute::ResetUnderline => self.effects.remove(Effects::UNDERLINES),
+            Attribute::ResetBlink => self.effects.remove(Effects::BLINK | Effects::RAPID_BLINK),
+            Attribute::ResetInverse => self.effects.remove(Effects::INVERSE),
+            Attribute::ResetInvisible => self.effects.remove(Effects::INVISIBLE),
+            Attribute::ResetStrike => self.effects.remove(Effects::STRIKE),
+            Attribute::SetFg(colour) => self.fg = Some(colour),
+            Attribute::ResetFg => self.fg = None,
+            Attribute::SetBg(colour) => self.bg = Some(colour),
+            Attribute::ResetBg => self.bg = None,
+            Attribute::Framed => self.effects.insert(Effects::FRAMED),
+            Attribute::Encircled => self.effects.insert(Effects::ENCIRCLED),
+            Attribute::Overlined => self.effects.insert(Effects::OVERLINED),
+            Attribute::ResetFramedEncircled => {
+                self.effects.remove(Effects::FRAMED | Effects::ENCIRCLED)
+            },
+            Attribute::ResetOverlined => self.effects.remove(Effects::OVERLINED),
+            Attribute::SetUl(colour) => self.ul = Some(colour),
+            Attribute::ResetUl => self.ul = None,
+            Attribute::IdeogramUnderline => self.effects.insert(Effects::IDEOGRAM_UNDERLINE),
+            Attribute::IdeogramDblUnderline => {
+                self.effects.insert(Effects::IDEOGRAM_DBL_UNDERLINE)
+            },
+            Attribute::IdeogramOverline => self.effects.insert(Effects::IDEOGRAM_OVERLINE),
+            Attribute::IdeogramDblOverline => self.effects.insert(Effects::IDEOGRAM_DBL_OVERLINE),
+            Attribute::IdeogramStressMarking => {
+                self.effects.insert(Effects::IDEOGRAM_STRESS_MARKING)
+            },
+            Attribute::ResetIdeogram => self.effects.remove(Effects::IDEOGRAM),
+            Attribute::UnknownUnderline(_) | Attribute::Unknown(_) => {},
+        }
+    }
+
+    /// Sets the underline style, replacing any other (terminals show just one at a time)
+    #[inline]
+    fn set_underline(&mut self, underline: Effects) {
+        self.effects.remove(Effects::UNDERLINES);
+        self.effects.insert(underline);
+    }
+
+    /// Is this style empty (such that it would apply nothing)?
+    #[inline]
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Writes the sequence applying the style
+///
+/// Nothing at all is written for a plain style.
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_plain() {
+            return Ok(());
+        }
+        let mut sep = Separator::new();
+        for &(effect, code) in EFFECT_CODES.iter() {
+            if self.effects.contains(effect) {
+                sep.next(f)?;
+                f.write_str(code)?;
+            }
+        }
+        if let Some(font) = self.font {
+            sep.next(f)?;
+            write!(f, "{}", 10 + font as u8)?;
+        }
+        if let Some(colour) = self.fg {
+            sep.next(f)?;
+            write!(f, "{}", colour.fg())?;
+        }
+        if let Some(colour) = self.bg {
+            sep.next(f)?;
+            write!(f, "{}", colour.bg())?;
+        }
+        if let Some(colour) = self.ul {
+            sep.next(f)?;
+            write!(f, "{}", colour.ul())?;
+        }
+        f.write_str(crate::codes::SEQ_POSTFIX)
+    }
+}
+
+/// Writes the sequence prefix before the first code, and a separator before each subsequent one
+struct Separator(bool);
+
+impl Separator {
+    #[inline]
+    fn new() -> Self {
+        Separator(false)
+    }
+
+    fn next(&mut self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            true => f.write_str(";"),
+            false => {
+                self.0 = true;
+                f.write_str(crate::codes::SEQ_PREFIX)
+            },
+        }
+    }
+}
@@ -8,6 +8,17 @@
 
 //! Formatted output support helpers
 
+use core::fmt;
+use core::str::FromStr;
+#[cfg(all(unix, feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(all(unix, feature = "std"))]
+use std::os::unix::io::RawFd;
+use crate::colour::ColourLevel;
+
+// Alias for the non-British-English speakers
+pub type ColorChoice = ColourChoice;
+
 /// Are ANSI format sequences supported on stdout?
 ///
 /// - On Unix this is reliable, returning `true` only if **stdout** is connected to a tty (as
@@ -52,6 +63,617 @@ pub fn use_fmt_stderr(user_pref: bool) -> bool {
     user_pref && fmt_supported_stderr()
 }
 
+/// What level of colour support does stdout have?
+///
+/// This checks whether stdout is connected to a tty, along with the environment variables
+/// described for [`colour_level()`].
+///
+/// This requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn colour_level_stdout() -> ColourLevel {
+    colour_level(fmt_supported_stdout(), |name| std::env::var(name).ok())
+}
+
+/// What level of colour support does stderr have?
+///
+/// This checks whether stderr is connected to a tty, along with the environment variables
+/// described for [`colour_level()`].
+///
+/// This requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn colour_level_stderr() -> ColourLevel {
+    colour_level(fmt_supported_stderr(), |name| std::env::var(name).ok())
+}
+
+/// What level of colour support does a stream have?
+///
+/// Takes whether or not the stream is connected to a tty, and a function for looking up
+/// environment variables (allowing something other than the actual environment to be used). The
+/// following conventions are followed:
+///
+/// - `CLICOLOR_FORCE`: If set to anything other than `0`, colour is to be used regardless of
+///   anything else, including the stream not being connected to a tty.
+/// - `NO_COLOR`: If set to anything non-empty, no colour is to be used.
+/// - `CLICOLOR`: If set to `0`, no colour is to be used.
+/// - `TERM`: If set to `dumb`, no colour is to be used. Otherwise a value containing `256color`
+///   indicates 256-colour support, and one ending in `-direct` or containing `truecolor` indicates
+///   RGB colour support.
+/// - `COLORTERM`: If set to `truecolor` or `24bit`, indicates RGB colour support.
+///
+/// Where colour is to be used but nothing indicates the level of support, the basic level of
+/// [`ColourLevel::Ansi16`] is assumed.
+///
+/// ```rust
+/// use term_ctrl::support::colour_level;
+/// use term_ctrl::colour::ColourLevel;
+///
+/// let env = [("TERM", "xterm-256color"), ("COLORTERM", "truecolor")];
+/// let lookup = |name: &str| env.iter().find(|e| e.0 == name).map(|e| e.1);
+/// assert_eq!(ColourLevel::TrueColour, colour_level(true, lookup));
+/// assert_eq!(ColourLevel::None, colour_level(false, lookup));
+/// ```
+pub fn colour_level<F, V>(is_tty: bool, env: F) -> ColourLevel
+    where F: Fn(&str) -> Option<V>, V: AsRef<str>
+{
+    // Checks whether a variable is set and its value satisfies a test
+    let var_is = |name, test: &dyn Fn(&str) -> bool| env(name).map_or(false, |v| test(v.as_ref()));
+
+    let forced = var_is("CLICOLOR_FORCE", &|v| !v.is_empty() && v != "0");
+    if !forced && (!is_tty
+        || var_is("NO_COLOR", &|v| !v.is_empty())
+        || var_is("CLICOLOR", &|v| v == "0")
+        || var_is("TERM", &|v| v == "dumb"))
+    {
+        return ColourLevel::None;
+    }
+    colour_depth(env)
+}
+
+/// Determines the level of colour support from the environment, on the basis that colour is to be
+/// used
+fn colour_depth<F, V>(env: F) -> ColourLevel
+    where F: Fn(&str) -> Option<V>, V: AsRef<str>
+{
+    let var_is = |name, test: &dyn Fn(&str) -> bool| env(name).map_or(false, |v| test(v.as_ref()));
+
+    if var_is("COLORTERM", &|v| v == "truecolor" || v == "24bit")
+        || var_is("TERM", &|v| v.ends_with("-direct") || v.contains("truecolor"))
+    {
+        return ColourLevel::TrueColour;
+    }
+    match var_is("TERM", &|v| v.contains("256color")) {
+        true => ColourLevel::Ansi256,
+        false => ColourLevel::Ansi16,
+    }
+}
+
+/// User choice of whether to use colour
+///
+/// This is the typical “always/auto/never” choice offered by a `--color` command line option.
+///
+/// ```rust
+/// use term_ctrl::support::ColourChoice;
+///
+/// assert_eq!(Ok(ColourChoice::Always), "always".parse());
+/// assert_eq!(Some(Ok(ColourChoice::Never)), ColourChoice::from_arg("--color=never"));
+/// assert_eq!(None, ColourChoice::from_arg("--verbose"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColourChoice {
+    /// Always use colour, even if not connected to a tty
+    Always,
+    /// Use colour if connected to a tty, and the environment does not say otherwise
+    Auto,
+    /// Never use colour
+    Never,
+}
+
+impl Default for ColourChoice {
+    #[inline]
+    fn default() -> Self {
+        ColourChoice::Auto
+    }
+}
+
+impl ColourChoice {
+    /// Parses a `--color` (or `--colour`) command line argument
+    ///
+    /// Gives `None` if the argument is not a colour option, otherwise the result of parsing the
+    /// value given with it (in `--color=WHEN` form). The option without a value is taken to mean
+    /// `always`.
+    pub fn from_arg(arg: &str) -> Option<Result<Self, ParseColourChoiceError>> {
+        let rest = match arg {
+            _ if arg.starts_with("--color") => &arg[7..],
+            _ if arg.starts_with("--colour") => &arg[8..],
+            _ => return None,
+        };
+        match rest {
+            "" => Some(Ok(ColourChoice::Always)),
+            _ if rest.starts_with('=') => Some(rest[1..].parse()),
+            _ => None,
+        }
+    }
+
+    /// Resolves the choice into the level of colour to use for a stream
+    ///
+    /// Takes whether or not the stream is connected to a tty, and a function for looking up
+    /// environment variables, as with [`colour_level()`]. With `Auto` this gives the same as that
+    /// function does, while with `Always` the tty check and the environment variables that turn
+    /// off colour are ignored; in that case the level still comes from the environment, but is
+    /// never [`ColourLevel::None`].
+    pub fn resolve<F, V>(self, is_tty: bool, env: F) -> ColourLevel
+        where F: Fn(&str) -> Option<V>, V: AsRef<str>
+    {
+        match self {
+            ColourChoice::Always => colour_depth(env),
+            ColourChoice::Auto => colour_level(is_tty, env),
+            ColourChoice::Never => ColourLevel::None,
+        }
+    }
+
+    /// Resolves the choice into the level of colour to use for stdout
+    ///
+    /// See [`resolve()`](Self::resolve). This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn resolve_stdout(self) -> ColourLevel {
+        self.resolve(fmt_supported_stdout(), |name| std::env::var(name).ok())
+    }
+
+    /// Resolves the choice into the level of colour to use for stderr
+    ///
+    /// See [`resolve()`](Self::resolve). This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn resolve_stderr(self) -> ColourLevel {
+        self.resolve(fmt_supported_stderr(), |name| std::env::var(name).ok())
+    }
+}
+
+/// Parses a choice, case-insensitively
+///
+/// Accepts `always`, `yes`, `force` or `ansi` for [`ColourChoice::Always`]; `auto`, `tty` or
+/// `if-tty` for [`ColourChoice::Auto`]; and `never`, `no` or `none` for [`ColourChoice::Never`].
+impl FromStr for ColourChoice {
+    type Err = ParseColourChoiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const CHOICES: [(&str, ColourChoice); 10] = [
+            ("always", ColourChoice::Always),
+            ("yes", ColourChoice::Always),
+            ("force", ColourChoice::Always),
+            ("ansi", ColourChoice::Always),
+            ("auto", ColourChoice::Auto),
+            ("tty", ColourChoice::Auto),
+            ("if-tty", ColourChoice::Auto),
+            ("never", ColourChoice::Never),
+            ("no", ColourChoice::Never),
+            ("none", ColourChoice::Never),
+        ];
+        CHOICES.iter()
+            .find(|c| c.0.eq_ignore_ascii_case(s))
+            .map(|c| c.1)
+            .ok_or(ParseColourChoiceError)
+    }
+}
+
+/// Error parsing a [`ColourChoice`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseColourChoiceError;
+
+impl fmt::Display for ParseColourChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid colour choice, expected one of `always`, `auto` or `never`")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColourChoiceError {}
+
+/// Terminal size
+///
+/// The pixel dimensions are zero where the terminal does not report them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TerminalSize {
+    /// Width in columns
+    pub cols: u16,
+    /// Height in rows
+    pub rows: u16,
+    /// Width in pixels
+    pub width: u16,
+    /// Height in pixels
+    pub height: u16,
+}
+
+/// Gets the size of the terminal a file descriptor is connected to
+///
+/// This uses the `TIOCGWINSZ` ioctl, giving `None` where this fails, such as where the file
+/// descriptor is not connected to a terminal, or where the terminal reports a size of zero.
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn terminal_size(fd: RawFd) -> Option<TerminalSize> {
+    let mut ws = libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: `TIOCGWINSZ` only writes to the `winsize` struct given
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut ws) } != 0 {
+        return None;
+    }
+    match ws.ws_col != 0 && ws.ws_row != 0 {
+        true => Some(TerminalSize {
+            cols: ws.ws_col,
+            rows: ws.ws_row,
+            width: ws.ws_xpixel,
+            height: ws.ws_ypixel,
+        }),
+        false => None,
+    }
+}
+
+/// Gets the terminal size from the `COLUMNS` and `LINES` environment variables
+///
+/// Shells typically set these (though may not export them) to the size of the terminal. As with
+/// [`colour_level()`], `env` is used to look up the variables. Both must be set to a non-zero
+/// number, otherwise this gives `None`.
+pub fn terminal_size_from_env<F, V>(env: F) -> Option<TerminalSize>
+    where F: Fn(&str) -> Option<V>, V: AsRef<str>
+{
+    let var = |name| {
+        env(name).and_then(|v| v.as_ref().trim().parse::<u16>().ok()).filter(|&n| n > 0)
+    };
+    Some(TerminalSize { cols: var("COLUMNS")?, rows: var("LINES")?, width: 0, height: 0 })
+}
+
+/// Gets the size of the terminal stdout is connected to
+///
+/// On Unix this uses [`terminal_size()`], falling back to [`terminal_size_from_env()`] with the
+/// process environment; elsewhere only the latter is used.
+///
+/// This requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn terminal_size_stdout() -> Option<TerminalSize> {
+    #[cfg(unix)]
+    if let Some(size) = terminal_size(libc::STDOUT_FILENO) {
+        return Some(size);
+    }
+    terminal_size_from_env(|name| std::env::var(name).ok())
+}
+
+/// Gets the size of the terminal stderr is connected to
+///
+/// On Unix this uses [`terminal_size()`], falling back to [`terminal_size_from_env()`] with the
+/// process environment; elsewhere only the latter is used.
+///
+/// This requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn terminal_size_stderr() -> Option<TerminalSize> {
+    #[cfg(unix)]
+    if let Some(size) = terminal_size(libc::STDERR_FILENO) {
+        return Some(size);
+    }
+    terminal_size_from_env(|name| std::env::var(name).ok())
+}
+
+/// Set by the `SIGWINCH` handler
+#[cfg(all(unix, feature = "std"))]
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// Starts watching for the terminal being resized
+///
+/// This installs a handler for the `SIGWINCH` signal, which the terminal sends on being resized,
+/// after which [`resized()`] reports whether it has been. Note that this replaces any handler
+/// already installed for the signal. Returns `false` if the handler could not be installed.
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn watch_resize() -> bool {
+    use std::os::raw::c_int;
+
+    extern "C" fn handler(_: c_int) {
+        RESIZED.store(true, Ordering::SeqCst);
+    }
+    // SAFETY: the action is fully initialised before use, and the handler only touches an atomic
+    unsafe {
+        let mut action: libc::sigaction = core::mem::zeroed();
+        action.sa_sigaction = handler as extern "C" fn(c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(libc::SIGWINCH, &action, core::ptr::null_mut()) == 0
+    }
+}
+
+/// Has the terminal been resized since last checked?
+///
+/// This reports, and clears, the flag set upon a resize, once watching has been started with
+/// [`watch_resize()`]. Typically, upon getting `true`, the size would then be fetched anew.
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+#[inline]
+pub fn resized() -> bool {
+    RESIZED.swap(false, Ordering::SeqCst)
+}
+
+/// Guard putting a Unix terminal into raw or cbreak mode
+///
+/// In raw mode, input is made available byte by byte as typed, rather than line by line, without
+/// being echoed, and without special keys such as Ctrl+C generating signals or output newlines
+/// being translated. Cbreak mode is more limited, just making input available as typed, without
+/// being echoed.
+///
+/// The original terminal settings are restored when the guard is dropped, including when dropped
+/// whilst unwinding from a panic.
+///
+/// ```rust,no_run
+/// use std::os::unix::io::AsRawFd;
+/// use term_ctrl::support::RawMode;
+///
+/// let _raw = RawMode::raw(std::io::stdin().as_raw_fd()).unwrap();
+/// // Read keys...
+/// ```
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub struct RawMode {
+    fd: RawFd,
+    original: libc::termios,
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl RawMode {
+    /// Puts the terminal a file descriptor is connected to into raw mode
+    pub fn raw(fd: RawFd) -> std::io::Result<Self> {
+        // SAFETY: `cfmakeraw` only modifies the `termios` struct given
+        Self::apply(fd, |termios| unsafe { libc::cfmakeraw(termios) })
+    }
+
+    /// Puts the terminal a file descriptor is connected to into cbreak mode
+    pub fn cbreak(fd: RawFd) -> std::io::Result<Self> {
+        Self::apply(fd, |termios| termios.c_lflag &= !(libc::ICANON | libc::ECHO))
+    }
+
+    /// Restores the original settings now, rather than waiting for drop
+    #[inline]
+    pub fn restore(self) -> std::io::Result<()> {
+        let result = set_termios(self.fd, &self.original);
+        core::mem::forget(self);
+        result
+    }
+
+    fn apply<F: FnOnce(&mut libc::termios)>(fd: RawFd, modify: F) -> std::io::Result<Self> {
+        let original = get_termios(fd)?;
+        let mut termios = original;
+        modify(&mut termios);
+        // Reads wait for at least one byte, without timing out
+        termios.c_cc[libc::VMIN] = 1;
+        termios.c_cc[libc::VTIME] = 0;
+        set_termios(fd, &termios)?;
+        Ok(Self { fd, original })
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = set_termios(self.fd, &self.original);
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl fmt::Debug for RawMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RawMode").field("fd", &self.fd).finish()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn get_termios(fd: RawFd) -> std::io::Result<libc::termios> {
+    let mut termios = core::mem::MaybeUninit::uninit();
+    // SAFETY: `tcgetattr` fully initialises the struct on success
+    match unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } {
+        0 => Ok(unsafe { termios.assume_init() }),
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn set_termios(fd: RawFd, termios: &libc::termios) -> std::io::Result<()> {
+    // Applying once pending output has been written, so as not to affect it
+    match unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, termios) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+/// Sends a query to a terminal and reads the reply
+///
+/// Since terminals ignore queries they do not support, a [`DA1`](crate::query::DA1) query, which
+/// virtually all terminals do reply to, is sent after the query as a sentinel. The escape sequence
+/// read directly before its reply is returned as the reply to the query, which is thus empty where
+/// the query is not supported. If the sentinel’s reply does not arrive within `timeout`, `None` is
+/// returned instead. (Where the query is itself DA1, its reply is returned.)
+///
+/// Along with the reply is given any other input read in the meantime, such as keys pressed by the
+/// user, which can then be processed as normal. (Note though that where the query is not
+/// supported, a key sending an escape sequence pressed just before the sentinel’s reply is taken as
+/// the reply.)
+///
+/// The terminal is put into cbreak mode for the duration, so that the reply is neither echoed nor
+/// held back awaiting a newline. The file descriptor must be open for both reading and writing.
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn round_trip(fd: RawFd, query: &str, timeout: std::time::Duration)
+    -> std::io::Result<(Option<std::vec::Vec<u8>>, std::vec::Vec<u8>)>
+{
+    use crate::query::DA1;
+
+    let _cbreak = RawMode::cbreak(fd)?;
+    let is_da1 = query == DA1;
+    write_fd(fd, query.as_bytes())?;
+    if !is_da1 {
+        write_fd(fd, DA1.as_bytes())?;
+    }
+    let mut buf = std::vec::Vec::new();
+    let (start, end) = match read_until(fd, timeout, &mut buf, find_da1_reply)? {
+        Some(found) => found,
+        None => return Ok((None, buf)),
+    };
+    let reply = if is_da1 {
+        buf.drain(start..end).collect()
+    } else {
+        buf.drain(start..end);
+        let reply_start = crate::scan::last_sequence_start(&buf[..start]).unwrap_or(start);
+        buf.drain(reply_start..start).collect()
+    };
+    Ok((Some(reply), buf))
+}
+
+/// Sends a query to the controlling terminal and reads the reply
+///
+/// This opens `/dev/tty`, and so works even where stdin and stdout are redirected, then does as
+/// described for [`round_trip()`].
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn round_trip_tty(query: &str, timeout: std::time::Duration)
+    -> std::io::Result<(Option<std::vec::Vec<u8>>, std::vec::Vec<u8>)>
+{
+    use std::os::unix::io::AsRawFd;
+
+    let tty = std::fs::OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    round_trip(tty.as_raw_fd(), query, timeout)
+}
+
+/// Finds a DA1 reply (`CSI ? ... c`), giving its start and end
+#[cfg(all(unix, feature = "std"))]
+fn find_da1_reply(buf: &[u8]) -> Option<(usize, usize)> {
+    let mut from = 0;
+    while let Some(pos) = buf[from..].windows(3).position(|w| w == b"\x1B[?") {
+        let start = from + pos;
+        let params = buf[start + 3..].iter().take_while(|&&b| b.is_ascii_digit() || b == b';');
+        let end = start + 3 + params.count();
+        if buf.get(end) == Some(&b'c') {
+            return Some((start, end + 1));
+        }
+        from = start + 1;
+    }
+    None
+}
+
+/// Asks a terminal for the cursor position
+///
+/// This sends a cursor position report query (see
+/// [`predefined::cursor::REPORT_POSITION`](crate::predefined::cursor::REPORT_POSITION)) and reads
+/// until the reply arrives, or `timeout` passes, giving the position (or `None` on timeout) along
+/// with any other input read in the meantime, such as keys pressed by the user, which can then be
+/// processed as normal.
+///
+/// The terminal is put into cbreak mode for the duration, as with [`round_trip()`].
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn cursor_position(fd: RawFd, timeout: std::time::Duration)
+    -> std::io::Result<(Option<crate::cursor::Position>, std::vec::Vec<u8>)>
+{
+    use crate::cursor;
+    use crate::predefined::cursor::REPORT_POSITION;
+
+    let _cbreak = RawMode::cbreak(fd)?;
+    write_fd(fd, REPORT_POSITION.as_bytes())?;
+    let mut buf = std::vec::Vec::new();
+    let found = read_until(fd, timeout, &mut buf, |buf| {
+        cursor::find_position_report(buf).map(|(_, range)| (range.start, range.end))
+    })?;
+    Ok(match found {
+        Some((start, end)) => {
+            let position = cursor::find_position_report(&buf[start..end]).map(|(p, _)| p);
+            buf.drain(start..end);
+            (position, buf)
+        },
+        None => (None, buf),
+    })
+}
+
+/// Asks the controlling terminal for the cursor position
+///
+/// This opens `/dev/tty`, then does as described for [`cursor_position()`].
+///
+/// This requires the `std` feature.
+#[cfg(all(unix, feature = "std"))]
+pub fn cursor_position_tty(timeout: std::time::Duration)
+    -> std::io::Result<(Option<crate::cursor::Position>, std::vec::Vec<u8>)>
+{
+    use std::os::unix::io::AsRawFd;
+
+    let tty = std::fs::OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    cursor_position(tty.as_raw_fd(), timeout)
+}
+
+/// Writes all of `data` to a file descriptor
+#[cfg(all(unix, feature = "std"))]
+fn write_fd(fd: RawFd, data: &[u8]) -> std::io::Result<()> {
+    use std::io;
+
+    let mut written = 0;
+    while written < data.len() {
+        let rest = &data[written..];
+        // SAFETY: the pointer and length are those of the unwritten part of `data`
+        let n = unsafe { libc::write(fd, rest.as_ptr() as *const libc::c_void, rest.len()) };
+        match n {
+            n if n >= 0 => written += n as usize,
+            _ if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => {},
+            _ => return Err(io::Error::last_os_error()),
+        }
+    }
+    Ok(())
+}
+
+/// Reads from a file descriptor into `buf` until `find` finds what is wanted in it, giving what
+/// `find` gives, or `None` upon timeout or end of input
+#[cfg(all(unix, feature = "std"))]
+fn read_until<F>(
+    fd: RawFd,
+    timeout: std::time::Duration,
+    buf: &mut std::vec::Vec<u8>,
+    find: F,
+) -> std::io::Result<Option<(usize, usize)>>
+    where F: Fn(&[u8]) -> Option<(usize, usize)>
+{
+    use std::io;
+    use std::os::raw::c_int;
+    use std::time::Instant;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(found) = find(buf) {
+            return Ok(Some(found));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        let ms = (deadline - now).as_millis().min(c_int::max_value() as u128) as c_int;
+        // SAFETY: exactly one `pollfd` is given
+        match unsafe { libc::poll(&mut pollfd, 1, ms) } {
+            0 => return Ok(None),
+            n if n < 0 => match io::Error::last_os_error() {
+                e if e.kind() == io::ErrorKind::Interrupted => continue,
+                e => return Err(e),
+            },
+            _ => {},
+        }
+        let mut chunk = [0u8; 256];
+        // SAFETY: the pointer and length are those of `chunk`
+        match unsafe { libc::read(fd, chunk.as_mut_ptr() as *mut libc::c_void, chunk.len()) } {
+            0 => return Ok(None),
+            n if n > 0 => buf.extend_from_slice(&chunk[..n as usize]),
+            _ => match io::Error::last_os_error() {
+                e if e.kind() == io::ErrorKind::Interrupted => {},
+                e => return Err(e),
+            },
+        }
+    }
+}
+
 /*
   Copied and slightly modified from the `ansi_term` crate (MIT licensed).
 */
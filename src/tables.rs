@@ -0,0 +1,169 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Character width tables
+//!
+//! Generated by `tools/gen_width_tables.py` from the Unicode Character Database, version
+//! 14.0.0. Do not edit by hand.
+
+/// Zero width chars (combining marks, format chars, and Hangul medial and final jamo)
+pub(crate) static ZERO_WIDTH: &[(char, char)] = &[
+    ('\u{300}', '\u{36F}'), ('\u{483}', '\u{489}'), ('\u{591}', '\u{5BD}'), ('\u{5BF}', '\u{5BF}'),
+    ('\u{5C1}', '\u{5C2}'), ('\u{5C4}', '\u{5C5}'), ('\u{5C7}', '\u{5C7}'), ('\u{600}', '\u{605}'),
+    ('\u{610}', '\u{61A}'), ('\u{61C}', '\u{61C}'), ('\u{64B}', '\u{65F}'), ('\u{670}', '\u{670}'),
+    ('\u{6D6}', '\u{6DD}'), ('\u{6DF}', '\u{6E4}'), ('\u{6E7}', '\u{6E8}'), ('\u{6EA}', '\u{6ED}'),
+    ('\u{70F}', '\u{70F}'), ('\u{711}', '\u{711}'), ('\u{730}', '\u{74A}'), ('\u{7A6}', '\u{7B0}'),
+    ('\u{7EB}', '\u{7F3}'), ('\u{7FD}', '\u{7FD}'), ('\u{816}', '\u{819}'), ('\u{81B}', '\u{823}'),
+    ('\u{825}', '\u{827}'), ('\u{829}', '\u{82D}'), ('\u{859}', '\u{85B}'), ('\u{890}', '\u{891}'),
+    ('\u{898}', '\u{89F}'), ('\u{8CA}', '\u{902}'), ('\u{93A}', '\u{93A}'), ('\u{93C}', '\u{93C}'),
+    ('\u{941}', '\u{948}'), ('\u{94D}', '\u{94D}'), ('\u{951}', '\u{957}'), ('\u{962}', '\u{963}'),
+    ('\u{981}', '\u{981}'), ('\u{9BC}', '\u{9BC}'), ('\u{9C1}', '\u{9C4}'), ('\u{9CD}', '\u{9CD}'),
+    ('\u{9E2}', '\u{9E3}'), ('\u{9FE}', '\u{9FE}'), ('\u{A01}', '\u{A02}'), ('\u{A3C}', '\u{A3C}'),
+    ('\u{A41}', '\u{A42}'), ('\u{A47}', '\u{A48}'), ('\u{A4B}', '\u{A4D}'), ('\u{A51}', '\u{A51}'),
+    ('\u{A70}', '\u{A71}'), ('\u{A75}', '\u{A75}'), ('\u{A81}', '\u{A82}'), ('\u{ABC}', '\u{ABC}'),
+    ('\u{AC1}', '\u{AC5}'), ('\u{AC7}', '\u{AC8}'), ('\u{ACD}', '\u{ACD}'), ('\u{AE2}', '\u{AE3}'),
+    ('\u{AFA}', '\u{AFF}'), ('\u{B01}', '\u{B01}'), ('\u{B3C}', '\u{B3C}'), ('\u{B3F}', '\u{B3F}'),
+    ('\u{B41}', '\u{B44}'), ('\u{B4D}', '\u{B4D}'), ('\u{B55}', '\u{B56}'), ('\u{B62}', '\u{B63}'),
+    ('\u{B82}', '\u{B82}'), ('\u{BC0}', '\u{BC0}'), ('\u{BCD}', '\u{BCD}'), ('\u{C00}', '\u{C00}'),
+    ('\u{C04}', '\u{C04}'), ('\u{C3C}', '\u{C3C}'), ('\u{C3E}', '\u{C40}'), ('\u{C46}', '\u{C48}'),
+    ('\u{C4A}', '\u{C4D}'), ('\u{C55}', '\u{C56}'), ('\u{C62}', '\u{C63}'), ('\u{C81}', '\u{C81}'),
+    ('\u{CBC}', '\u{CBC}'), ('\u{CBF}', '\u{CBF}'), ('\u{CC6}', '\u{CC6}'), ('\u{CCC}', '\u{CCD}'),
+    ('\u{CE2}', '\u{CE3}'), ('\u{D00}', '\u{D01}'), ('\u{D3B}', '\u{D3C}'), ('\u{D41}', '\u{D44}'),
+    ('\u{D4D}', '\u{D4D}'), ('\u{D62}', '\u{D63}'), ('\u{D81}', '\u{D81}'), ('\u{DCA}', '\u{DCA}'),
+    ('\u{DD2}', '\u{DD4}'), ('\u{DD6}', '\u{DD6}'), ('\u{E31}', '\u{E31}'), ('\u{E34}', '\u{E3A}'),
+    ('\u{E47}', '\u{E4E}'), ('\u{EB1}', '\u{EB1}'), ('\u{EB4}', '\u{EBC}'), ('\u{EC8}', '\u{ECD}'),
+    ('\u{F18}', '\u{F19}'), ('\u{F35}', '\u{F35}'), ('\u{F37}', '\u{F37}'), ('\u{F39}', '\u{F39}'),
+    ('\u{F71}', '\u{F7E}'), ('\u{F80}', '\u{F84}'), ('\u{F86}', '\u{F87}'), ('\u{F8D}', '\u{F97}'),
+    ('\u{F99}', '\u{FBC}'), ('\u{FC6}', '\u{FC6}'), ('\u{102D}', '\u{1030}'),
+    ('\u{1032}', '\u{1037}'), ('\u{1039}', '\u{103A}'), ('\u{103D}', '\u{103E}'),
+    ('\u{1058}', '\u{1059}'), ('\u{105E}', '\u{1060}'), ('\u{1071}', '\u{1074}'),
+    ('\u{1082}', '\u{1082}'), ('\u{1085}', '\u{1086}'), ('\u{108D}', '\u{108D}'),
+    ('\u{109D}', '\u{109D}'), ('\u{1160}', '\u{11FF}'), ('\u{135D}', '\u{135F}'),
+    ('\u{1712}', '\u{1714}'), ('\u{1732}', '\u{1733}'), ('\u{1752}', '\u{1753}'),
+    ('\u{1772}', '\u{1773}'), ('\u{17B4}', '\u{17B5}'), ('\u{17B7}', '\u{17BD}'),
+    ('\u{17C6}', '\u{17C6}'), ('\u{17C9}', '\u{17D3}'), ('\u{17DD}', '\u{17DD}'),
+    ('\u{180B}', '\u{180F}'), ('\u{1885}', '\u{1886}'), ('\u{18A9}', '\u{18A9}'),
+    ('\u{1920}', '\u{1922}'), ('\u{1927}', '\u{1928}'), ('\u{1932}', '\u{1932}'),
+    ('\u{1939}', '\u{193B}'), ('\u{1A17}', '\u{1A18}'), ('\u{1A1B}', '\u{1A1B}'),
+    ('\u{1A56}', '\u{1A56}'), ('\u{1A58}', '\u{1A5E}'), ('\u{1A60}', '\u{1A60}'),
+    ('\u{1A62}', '\u{1A62}'), ('\u{1A65}', '\u{1A6C}'), ('\u{1A73}', '\u{1A7C}'),
+    ('\u{1A7F}', '\u{1A7F}'), ('\u{1AB0}', '\u{1ACE}'), ('\u{1B00}', '\u{1B03}'),
+    ('\u{1B34}', '\u{1B34}'), ('\u{1B36}', '\u{1B3A}'), ('\u{1B3C}', '\u{1B3C}'),
+    ('\u{1B42}', '\u{1B42}'), ('\u{1B6B}', '\u{1B73}'), ('\u{1B80}', '\u{1B81}'),
+    ('\u{1BA2}', '\u{1BA5}'), ('\u{1BA8}', '\u{1BA9}'), ('\u{1BAB}', '\u{1BAD}'),
+    ('\u{1BE6}', '\u{1BE6}'), ('\u{1BE8}', '\u{1BE9}'), ('\u{1BED}', '\u{1BED}'),
+    ('\u{1BEF}', '\u{1BF1}'), ('\u{1C2C}', '\u{1C33}'), ('\u{1C36}', '\u{1C37}'),
+    ('\u{1CD0}', '\u{1CD2}'), ('\u{1CD4}', '\u{1CE0}'), ('\u{1CE2}', '\u{1CE8}'),
+    ('\u{1CED}', '\u{1CED}'), ('\u{1CF4}', '\u{1CF4}'), ('\u{1CF8}', '\u{1CF9}'),
+    ('\u{1DC0}', '\u{1DFF}'), ('\u{200B}', '\u{200F}'), ('\u{202A}', '\u{202E}'),
+    ('\u{2060}', '\u{2064}'), ('\u{2066}', '\u{206F}'), ('\u{20D0}', '\u{20F0}'),
+    ('\u{2CEF}', '\u{2CF1}'), ('\u{2D7F}', '\u{2D7F}'), ('\u{2DE0}', '\u{2DFF}'),
+    ('\u{302A}', '\u{302D}'), ('\u{3099}', '\u{309A}'), ('\u{A66F}', '\u{A672}'),
+    ('\u{A674}', '\u{A67D}'), ('\u{A69E}', '\u{A69F}'), ('\u{A6F0}', '\u{A6F1}'),
+    ('\u{A802}', '\u{A802}'), ('\u{A806}', '\u{A806}'), ('\u{A80B}', '\u{A80B}'),
+    ('\u{A825}', '\u{A826}'), ('\u{A82C}', '\u{A82C}'), ('\u{A8C4}', '\u{A8C5}'),
+    ('\u{A8E0}', '\u{A8F1}'), ('\u{A8FF}', '\u{A8FF}'), ('\u{A926}', '\u{A92D}'),
+    ('\u{A947}', '\u{A951}'), ('\u{A980}', '\u{A982}'), ('\u{A9B3}', '\u{A9B3}'),
+    ('\u{A9B6}', '\u{A9B9}'), ('\u{A9BC}', '\u{A9BD}'), ('\u{A9E5}', '\u{A9E5}'),
+    ('\u{AA29}', '\u{AA2E}'), ('\u{AA31}', '\u{AA32}'), ('\u{AA35}', '\u{AA36}'),
+    ('\u{AA43}', '\u{AA43}'), ('\u{AA4C}', '\u{AA4C}'), ('\u{AA7C}', '\u{AA7C}'),
+    ('\u{AAB0}', '\u{AAB0}'), ('\u{AAB2}', '\u{AAB4}'), ('\u{AAB7}', '\u{AAB8}'),
+    ('\u{AABE}', '\u{AABF}'), ('\u{AAC1}', '\u{AAC1}'), ('\u{AAEC}', '\u{AAED}'),
+    ('\u{AAF6}', '\u{AAF6}'), ('\u{ABE5}', '\u{ABE5}'), ('\u{ABE8}', '\u{ABE8}'),
+    ('\u{ABED}', '\u{ABED}'), ('\u{FB1E}', '\u{FB1E}'), ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'), ('\u{FEFF}', '\u{FEFF}'), ('\u{FFF9}', '\u{FFFB}'),
+    ('\u{101FD}', '\u{101FD}'), ('\u{102E0}', '\u{102E0}'), ('\u{10376}', '\u{1037A}'),
+    ('\u{10A01}', '\u{10A03}'), ('\u{10A05}', '\u{10A06}'), ('\u{10A0C}', '\u{10A0F}'),
+    ('\u{10A38}', '\u{10A3A}'), ('\u{10A3F}', '\u{10A3F}'), ('\u{10AE5}', '\u{10AE6}'),
+    ('\u{10D24}', '\u{10D27}'), ('\u{10EAB}', '\u{10EAC}'), ('\u{10F46}', '\u{10F50}'),
+    ('\u{10F82}', '\u{10F85}'), ('\u{11001}', '\u{11001}'), ('\u{11038}', '\u{11046}'),
+    ('\u{11070}', '\u{11070}'), ('\u{11073}', '\u{11074}'), ('\u{1107F}', '\u{11081}'),
+    ('\u{110B3}', '\u{110B6}'), ('\u{110B9}', '\u{110BA}'), ('\u{110BD}', '\u{110BD}'),
+    ('\u{110C2}', '\u{110C2}'), ('\u{110CD}', '\u{110CD}'), ('\u{11100}', '\u{11102}'),
+    ('\u{11127}', '\u{1112B}'), ('\u{1112D}', '\u{11134}'), ('\u{11173}', '\u{11173}'),
+    ('\u{11180}', '\u{11181}'), ('\u{111B6}', '\u{111BE}'), ('\u{111C9}', '\u{111CC}'),
+    ('\u{111CF}', '\u{111CF}'), ('\u{1122F}', '\u{11231}'), ('\u{11234}', '\u{11234}'),
+    ('\u{11236}', '\u{11237}'), ('\u{1123E}', '\u{1123E}'), ('\u{112DF}', '\u{112DF}'),
+    ('\u{112E3}', '\u{112EA}'), ('\u{11300}', '\u{11301}'), ('\u{1133B}', '\u{1133C}'),
+    ('\u{11340}', '\u{11340}'), ('\u{11366}', '\u{1136C}'), ('\u{11370}', '\u{11374}'),
+    ('\u{11438}', '\u{1143F}'), ('\u{11442}', '\u{11444}'), ('\u{11446}', '\u{11446}'),
+    ('\u{1145E}', '\u{1145E}'), ('\u{114B3}', '\u{114B8}'), ('\u{114BA}', '\u{114BA}'),
+    ('\u{114BF}', '\u{114C0}'), ('\u{114C2}', '\u{114C3}'), ('\u{115B2}', '\u{115B5}'),
+    ('\u{115BC}', '\u{115BD}'), ('\u{115BF}', '\u{115C0}'), ('\u{115DC}', '\u{115DD}'),
+    ('\u{11633}', '\u{1163A}'), ('\u{1163D}', '\u{1163D}'), ('\u{1163F}', '\u{11640}'),
+    ('\u{116AB}', '\u{116AB}'), ('\u{116AD}', '\u{116AD}'), ('\u{116B0}', '\u{116B5}'),
+    ('\u{116B7}', '\u{116B7}'), ('\u{1171D}', '\u{1171F}'), ('\u{11722}', '\u{11725}'),
+    ('\u{11727}', '\u{1172B}'), ('\u{1182F}', '\u{11837}'), ('\u{11839}', '\u{1183A}'),
+    ('\u{1193B}', '\u{1193C}'), ('\u{1193E}', '\u{1193E}'), ('\u{11943}', '\u{11943}'),
+    ('\u{119D4}', '\u{119D7}'), ('\u{119DA}', '\u{119DB}'), ('\u{119E0}', '\u{119E0}'),
+    ('\u{11A01}', '\u{11A0A}'), ('\u{11A33}', '\u{11A38}'), ('\u{11A3B}', '\u{11A3E}'),
+    ('\u{11A47}', '\u{11A47}'), ('\u{11A51}', '\u{11A56}'), ('\u{11A59}', '\u{11A5B}'),
+    ('\u{11A8A}', '\u{11A96}'), ('\u{11A98}', '\u{11A99}'), ('\u{11C30}', '\u{11C36}'),
+    ('\u{11C38}', '\u{11C3D}'), ('\u{11C3F}', '\u{11C3F}'), ('\u{11C92}', '\u{11CA7}'),
+    ('\u{11CAA}', '\u{11CB0}'), ('\u{11CB2}', '\u{11CB3}'), ('\u{11CB5}', '\u{11CB6}'),
+    ('\u{11D31}', '\u{11D36}'), ('\u{11D3A}', '\u{11D3A}'), ('\u{11D3C}', '\u{11D3D}'),
+    ('\u{11D3F}', '\u{11D45}'), ('\u{11D47}', '\u{11D47}'), ('\u{11D90}', '\u{11D91}'),
+    ('\u{11D95}', '\u{11D95}'), ('\u{11D97}', '\u{11D97}'), ('\u{11EF3}', '\u{11EF4}'),
+    ('\u{13430}', '\u{13438}'), ('\u{16AF0}', '\u{16AF4}'), ('\u{16B30}', '\u{16B36}'),
+    ('\u{16F4F}', '\u{16F4F}'), ('\u{16F8F}', '\u{16F92}'), ('\u{16FE4}', '\u{16FE4}'),
+    ('\u{1BC9D}', '\u{1BC9E}'), ('\u{1BCA0}', '\u{1BCA3}'), ('\u{1CF00}', '\u{1CF2D}'),
+    ('\u{1CF30}', '\u{1CF46}'), ('\u{1D167}', '\u{1D169}'), ('\u{1D173}', '\u{1D182}'),
+    ('\u{1D185}', '\u{1D18B}'), ('\u{1D1AA}', '\u{1D1AD}'), ('\u{1D242}', '\u{1D244}'),
+    ('\u{1DA00}', '\u{1DA36}'), ('\u{1DA3B}', '\u{1DA6C}'), ('\u{1DA75}', '\u{1DA75}'),
+    ('\u{1DA84}', '\u{1DA84}'), ('\u{1DA9B}', '\u{1DA9F}'), ('\u{1DAA1}', '\u{1DAAF}'),
+    ('\u{1E000}', '\u{1E006}'), ('\u{1E008}', '\u{1E018}'), ('\u{1E01B}', '\u{1E021}'),
+    ('\u{1E023}', '\u{1E024}'), ('\u{1E026}', '\u{1E02A}'), ('\u{1E130}', '\u{1E136}'),
+    ('\u{1E2AE}', '\u{1E2AE}'), ('\u{1E2EC}', '\u{1E2EF}'), ('\u{1E8D0}', '\u{1E8D6}'),
+    ('\u{1E944}', '\u{1E94A}'), ('\u{E0001}', '\u{E0001}'), ('\u{E0020}', '\u{E007F}'),
+    ('\u{E0100}', '\u{E01EF}'),
+];
+
+/// Wide chars (East Asian Wide and Fullwidth)
+pub(crate) static WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'), ('\u{231A}', '\u{231B}'), ('\u{2329}', '\u{232A}'),
+    ('\u{23E9}', '\u{23EC}'), ('\u{23F0}', '\u{23F0}'), ('\u{23F3}', '\u{23F3}'),
+    ('\u{25FD}', '\u{25FE}'), ('\u{2614}', '\u{2615}'), ('\u{2648}', '\u{2653}'),
+    ('\u{267F}', '\u{267F}'), ('\u{2693}', '\u{2693}'), ('\u{26A1}', '\u{26A1}'),
+    ('\u{26AA}', '\u{26AB}'), ('\u{26BD}', '\u{26BE}'), ('\u{26C4}', '\u{26C5}'),
+    ('\u{26CE}', '\u{26CE}'), ('\u{26D4}', '\u{26D4}'), ('\u{26EA}', '\u{26EA}'),
+    ('\u{26F2}', '\u{26F3}'), ('\u{26F5}', '\u{26F5}'), ('\u{26FA}', '\u{26FA}'),
+    ('\u{26FD}', '\u{26FD}'), ('\u{2705}', '\u{2705}'), ('\u{270A}', '\u{270B}'),
+    ('\u{2728}', '\u{2728}'), ('\u{274C}', '\u{274C}'), ('\u{274E}', '\u{274E}'),
+    ('\u{2753}', '\u{2755}'), ('\u{2757}', '\u{2757}'), ('\u{2795}', '\u{2797}'),
+    ('\u{27B0}', '\u{27B0}'), ('\u{27BF}', '\u{27BF}'), ('\u{2B1B}', '\u{2B1C}'),
+    ('\u{2B50}', '\u{2B50}'), ('\u{2B55}', '\u{2B55}'), ('\u{2E80}', '\u{2E99}'),
+    ('\u{2E9B}', '\u{2EF3}'), ('\u{2F00}', '\u{2FD5}'), ('\u{2FF0}', '\u{2FFB}'),
+    ('\u{3000}', '\u{3029}'), ('\u{302E}', '\u{303E}'), ('\u{3041}', '\u{3096}'),
+    ('\u{309B}', '\u{30FF}'), ('\u{3105}', '\u{312F}'), ('\u{3131}', '\u{318E}'),
+    ('\u{3190}', '\u{31E3}'), ('\u{31F0}', '\u{321E}'), ('\u{3220}', '\u{3247}'),
+    ('\u{3250}', '\u{4DBF}'), ('\u{4E00}', '\u{A48C}'), ('\u{A490}', '\u{A4C6}'),
+    ('\u{A960}', '\u{A97C}'), ('\u{AC00}', '\u{D7A3}'), ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE10}', '\u{FE19}'), ('\u{FE30}', '\u{FE52}'), ('\u{FE54}', '\u{FE66}'),
+    ('\u{FE68}', '\u{FE6B}'), ('\u{FF01}', '\u{FF60}'), ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{16FE0}', '\u{16FE3}'), ('\u{16FF0}', '\u{16FF1}'), ('\u{17000}', '\u{187F7}'),
+    ('\u{18800}', '\u{18CD5}'), ('\u{18D00}', '\u{18D08}'), ('\u{1AFF0}', '\u{1AFF3}'),
+    ('\u{1AFF5}', '\u{1AFFB}'), ('\u{1AFFD}', '\u{1AFFE}'), ('\u{1B000}', '\u{1B122}'),
+    ('\u{1B150}', '\u{1B152}'), ('\u{1B164}', '\u{1B167}'), ('\u{1B170}', '\u{1B2FB}'),
+    ('\u{1F004}', '\u{1F004}'), ('\u{1F0CF}', '\u{1F0CF}'), ('\u{1F18E}', '\u{1F18E}'),
+    ('\u{1F191}', '\u{1F19A}'), ('\u{1F200}', '\u{1F202}'), ('\u{1F210}', '\u{1F23B}'),
+    ('\u{1F240}', '\u{1F248}'), ('\u{1F250}', '\u{1F251}'), ('\u{1F260}', '\u{1F265}'),
+    ('\u{1F300}', '\u{1F320}'), ('\u{1F32D}', '\u{1F335}'), ('\u{1F337}', '\u{1F37C}'),
+    ('\u{1F37E}', '\u{1F393}'), ('\u{1F3A0}', '\u{1F3CA}'), ('\u{1F3CF}', '\u{1F3D3}'),
+    ('\u{1F3E0}', '\u{1F3F0}'), ('\u{1F3F4}', '\u{1F3F4}'), ('\u{1F3F8}', '\u{1F43E}'),
+    ('\u{1F440}', '\u{1F440}'), ('\u{1F442}', '\u{1F4FC}'), ('\u{1F4FF}', '\u{1F53D}'),
+    ('\u{1F54B}', '\u{1F54E}'), ('\u{1F550}', '\u{1F567}'), ('\u{1F57A}', '\u{1F57A}'),
+    ('\u{1F595}', '\u{1F596}'), ('\u{1F5A4}', '\u{1F5A4}'), ('\u{1F5FB}', '\u{1F64F}'),
+    ('\u{1F680}', '\u{1F6C5}'), ('\u{1F6CC}', '\u{1F6CC}'), ('\u{1F6D0}', '\u{1F6D2}'),
+    ('\u{1F6D5}', '\u{1F6D7}'), ('\u{1F6DD}', '\u{1F6DF}'), ('\u{1F6EB}', '\u{1F6EC}'),
+    ('\u{1F6F4}', '\u{1F6FC}'), ('\u{1F7E0}', '\u{1F7EB}'), ('\u{1F7F0}', '\u{1F7F0}'),
+    ('\u{1F90C}', '\u{1F93A}'), ('\u{1F93C}', '\u{1F945}'), ('\u{1F947}', '\u{1F9FF}'),
+    ('\u{1FA70}', '\u{1FA74}'), ('\u{1FA78}', '\u{1FA7C}'), ('\u{1FA80}', '\u{1FA86}'),
+    ('\u{1FA90}', '\u{1FAAC}'), ('\u{1FAB0}', '\u{1FABA}'), ('\u{1FAC0}', '\u{1FAC5}'),
+    ('\u{1FAD0}', '\u{1FAD9}'), ('\u{1FAE0}', '\u{1FAE7}'), ('\u{1FAF0}', '\u{1FAF6}'),
+    ('\u{20000}', '\u{2FFFD}'), ('\u{30000}', '\u{3FFFD}'),
+];
@@ -0,0 +1,88 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Window and tab titles
+//!
+//! The [`Title`] type here writes out the OSC 0, 1 or 2 sequence setting the window title and/or
+//! the icon name (which many terminals use as the tab title). Any control chars in the text are
+//! dropped, since they could otherwise end the sequence early or be otherwise misinterpreted.
+//!
+//! To restore the user’s original title afterwards, the titles can be saved to the terminal’s title
+//! stack beforehand and restored from it at exit, with the sequences in the `predefined::title` mod
+//! (an xterm feature, supported by many but not all terminals).
+//!
+//! ```rust
+//! use term_ctrl::title::Title;
+//! use term_ctrl::predefined::title::{PUSH, POP};
+//!
+//! print!("{}{}", PUSH, Title::new("Building… 42%"));
+//! // ...
+//! print!("{}", POP);
+//! assert_eq!("\u{1B}]0;Build\u{1B}\\", format!("{}", Title::new("Build\n")));
+//! ```
+
+use core::fmt;
+use crate::osc::{Terminator, OSC_PREFIX};
+
+/// What a title sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// Both the icon name and the window title (OSC 0)
+    Both = 0,
+    /// Just the icon name (OSC 1), often used as the tab title
+    IconName = 1,
+    /// Just the window title (OSC 2)
+    Window = 2,
+}
+
+impl Default for Target {
+    #[inline]
+    fn default() -> Self {
+        Target::Both
+    }
+}
+
+/// Title setting sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Title<'a> {
+    text: &'a str,
+    target: Target,
+    terminator: Terminator,
+}
+
+impl<'a> Title<'a> {
+    /// Creates a sequence setting both the icon name and window title to `text`
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self { text, target: Target::default(), terminator: Terminator::default() }
+    }
+
+    /// Sets what is set by the sequence
+    #[inline]
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the sequence terminator used (defaults to ST)
+    #[inline]
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl<'a> fmt::Display for Title<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{};", OSC_PREFIX, self.target as u8)?;
+        for part in self.text.split(char::is_control) {
+            f.write_str(part)?;
+        }
+        f.write_str(self.terminator.as_str())
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Display width
+//!
+//! Padding with the standard formatting width parameter (e.g. `{:<20}`) counts every char of a
+//! string, including those of any escape sequences within it, and also does not account for chars
+//! that occupy two columns or none at all. The helpers here measure text as displayed in a
+//! terminal instead, skipping escape sequences, and treating East Asian wide chars as two columns
+//! and combining marks and other such zero width chars as none.
+//!
+//! ```rust
+//! use term_ctrl::width::{visible_width, Padded};
+//! use term_ctrl::predefined::{RESET, colours::fg::RED};
+//!
+//! let text = format!("{}Error{}", RED, RESET);
+//! assert_eq!(5, visible_width(&text));
+//! assert_eq!(format!("{}Error{}   |", RED, RESET), format!("{:<8}|", Padded(&text)));
+//! ```
+
+use core::fmt::{self, Write};
+use crate::scan::Scanner;
+use crate::tables;
+
+/// Gets the number of columns a char occupies in a terminal
+///
+/// Control chars are treated as having zero width.
+pub fn char_width(c: char) -> usize {
+    match c {
+        '\u{0}'..='\u{1F}' | '\u{7F}'..='\u{9F}' => 0,
+        ' '..='\u{7E}' => 1,
+        _ if in_table(tables::ZERO_WIDTH, c) => 0,
+        _ if in_table(tables::WIDE, c) => 2,
+        _ => 1,
+    }
+}
+
+/// Gets the number of columns text occupies in a terminal, ignoring escape sequences
+pub fn visible_width(text: &str) -> usize {
+    let mut scanner = Scanner::new();
+    text.chars()
+        // The scanner state only ever changes on ASCII bytes, so feeding just the first byte of
+        // each char is enough to classify it
+        .filter(|&c| scanner.feed(first_byte(c)))
+        .map(char_width)
+        .sum()
+}
+
+/// Pads text containing escape sequences, according to its display width
+///
+/// This honours the width, fill and alignment parameters of the format string, just as with a
+/// plain string, but measures the text with [`visible_width()`]. Alignment is to the left by
+/// default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Padded<'a>(pub &'a str);
+
+impl<'a> fmt::Display for Padded<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let padding = f.width().unwrap_or(0).saturating_sub(visible_width(self.0));
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        f.write_str(self.0)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+#[inline]
+fn first_byte(c: char) -> u8 {
+    let mut buf = [0; 4];
+    c.encode_utf8(&mut buf);
+    buf[0]
+}
+
+fn in_table(table: &[(char, char)], c: char) -> bool {
+    table.binary_search_by(|&(start, end)| {
+        if c < start {
+            core::cmp::Ordering::Greater
+        } else if c > end {
+            core::cmp::Ordering::Less
+        } else {
+            core::cmp::Ordering::Equal
+        }
+    }).is_ok()
+}
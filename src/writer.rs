@@ -0,0 +1,121 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Stream writer
+//!
+//! Rather than filtering every sequence used as shown in the crate level documentation, output can
+//! instead be written through a [`Writer`], which checks support just the once on creation, and
+//! where formatting is not to be used, strips all escape sequences from everything written through
+//! it.
+//!
+//! ```rust
+//! use std::io::Write;
+//! use term_ctrl::writer::Writer;
+//! use term_ctrl::predefined::{RESET, colours::fg::RED};
+//!
+//! let mut out = Writer::stdout(true);
+//! writeln!(out, "{}Error:{} You made an error!", RED, RESET).unwrap();
+//! ```
+//!
+//! This requires the `std` feature.
+
+use std::io::{self, Write};
+use crate::scan::Scanner;
+use crate::support;
+
+/// Writer that passes through, or strips, escape sequences
+///
+/// Stripping works across writes, so a sequence split over more than one call to `write` is still
+/// removed correctly.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    inner: W,
+    strip: bool,
+    scanner: Scanner,
+}
+
+impl Writer<io::Stdout> {
+    /// Creates a writer for stdout
+    ///
+    /// Stripping is enabled according to [`support::use_fmt_stdout()`].
+    pub fn stdout(user_pref: bool) -> Self {
+        Self::new(io::stdout(), !support::use_fmt_stdout(user_pref))
+    }
+}
+
+impl Writer<io::Stderr> {
+    /// Creates a writer for stderr
+    ///
+    /// Stripping is enabled according to [`support::use_fmt_stderr()`].
+    pub fn stderr(user_pref: bool) -> Self {
+        Self::new(io::stderr(), !support::use_fmt_stderr(user_pref))
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer wrapping `inner`, stripping escape sequences if `strip` is `true`
+    #[inline]
+    pub fn new(inner: W, strip: bool) -> Self {
+        Self { inner, strip, scanner: Scanner::new() }
+    }
+
+    /// Are escape sequences being stripped?
+    #[inline]
+    pub fn is_stripping(&self) -> bool {
+        self.strip
+    }
+
+    /// Gets a reference to the underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer
+    ///
+    /// Note that writing directly to the underlying writer bypasses stripping.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps the writer, returning the underlying writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.strip {
+            return self.inner.write(buf);
+        }
+        // Write out each run of text
+        let mut run_start = None;
+        for (i, &b) in buf.iter().enumerate() {
+            match (self.scanner.feed(b), run_start) {
+                (true, None) => run_start = Some(i),
+                (false, Some(start)) => {
+                    self.inner.write_all(&buf[start..i])?;
+                    run_start = None;
+                },
+                _ => {},
+            }
+        }
+        if let Some(start) = run_start {
+            self.inner.write_all(&buf[start..])?;
+        }
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Unit tests of the process-wide current background
+//!
+//! These are kept apart from the other tests, which run in parallel within the same process.
+
+extern crate term_ctrl;
+
+/// Check adaptive colours resolving against the current background as they are written out
+///
+/// This is the only test here, so nothing else can change the current background meanwhile.
+#[test]
+fn current() {
+    use term_ctrl::background::{Adaptive, Background};
+    use term_ctrl::colour::{BasicColour, Colour, ColourLevel};
+    use term_ctrl::style::Style;
+
+    const WARNING: Adaptive = Adaptive {
+        light: Colour::Rgb(175, 95, 0),
+        dark: Colour::Basic(BasicColour::Yellow),
+    };
+    let style = Style::new().fg(&WARNING).bg(&WARNING);
+    assert_eq!(style.fg, Some(Colour::Adaptive(&WARNING)));
+
+    assert_eq!(Background::current(), Background::Unknown);
+    assert_eq!(format!("{}", style), "\u{1B}[33;43m");
+    Background::Light.set_current();
+    assert_eq!(Background::current(), Background::Light);
+    assert_eq!(format!("{}", style), "\u{1B}[38;2;175;95;0;48;2;175;95;0m");
+    assert_eq!(format!("{}", Colour::Adaptive(&WARNING).ul()), "58;2;175;95;0");
+
+    // Kept as is where both variants are supported
+    assert_eq!(Colour::Adaptive(&WARNING).downgrade(ColourLevel::TrueColour),
+        Some(Colour::Adaptive(&WARNING)));
+    // Otherwise resolved now
+    assert_eq!(Colour::Adaptive(&WARNING).downgrade(ColourLevel::Ansi256),
+        Some(Colour::Indexed(130)));
+    assert_eq!(Colour::Adaptive(&WARNING).downgrade(ColourLevel::None), None);
+
+    Background::Dark.set_current();
+    assert_eq!(format!("{}", style), "\u{1B}[33;43m");
+
+    // Adaptive variants are taken as the default colour, rather than recursed into
+    static NESTED: Adaptive = Adaptive { light: Colour::Adaptive(&NESTED), dark: Colour::Default };
+    assert_eq!(format!("{}", Colour::Adaptive(&NESTED).fg()), "39");
+    Background::Light.set_current();
+    assert_eq!(format!("{}", Colour::Adaptive(&NESTED).fg()), "39");
+    // (Not compared with `==`, which would recurse)
+    match Colour::Adaptive(&NESTED).downgrade(ColourLevel::Ansi16) {
+        Some(Colour::Adaptive(adaptive)) => assert!(std::ptr::eq(adaptive, &NESTED)),
+        other => panic!("unexpected downgrade: {:?}", other.map(|c| c.fg().to_string())),
+    }
+}
@@ -20,6 +20,486 @@ fn term_seq() {
     assert_eq!(seq!(4,8,15,16,23,42), "\u{1B}[4;8;15;16;23;42m");
 }
 
+/// Check cursor movement macros and types match
+#[test]
+fn cursor() {
+    use term_ctrl::cursor::*;
+
+    assert_eq!(format!("{}", Up(2)), cursor_up!(2));
+    assert_eq!(format!("{}", Down(10)), cursor_down!(10));
+    assert_eq!(format!("{}", Forward(1)), cursor_forward!(1));
+    assert_eq!(format!("{}", Back(300)), cursor_back!(300));
+    assert_eq!(format!("{}", NextLine(4)), cursor_next_line!(4));
+    assert_eq!(format!("{}", PrevLine(5)), cursor_prev_line!(5));
+    assert_eq!(format!("{}", Column(80)), cursor_column!(80));
+    assert_eq!(format!("{}", Position::new(24, 80)), cursor_pos!(24, 80));
+    assert_eq!(format!("{}", HvPosition::new(1, 2)), cursor_hv_pos!(1, 2));
+    assert_eq!(cursor_pos!(1, 2), "\u{1B}[1;2H");
+    assert_eq!(cursor_up!(), "\u{1B}[A");
+    assert_eq!(predefined::cursor::SAVE, "\u{1B}7");
+    assert_eq!(predefined::cursor::RESTORE_SCO, "\u{1B}[u");
+}
+
+/// Check erase types and predefines match
+#[test]
+fn erase() {
+    use term_ctrl::erase::{Screen, Line, Chars};
+    use predefined::erase;
+
+    assert_eq!(format!("{}", Screen::Below), erase::SCREEN_BELOW);
+    assert_eq!(format!("{}", Screen::Above), erase::SCREEN_ABOVE);
+    assert_eq!(format!("{}", Screen::All), erase::SCREEN);
+    assert_eq!(format!("{}", Screen::Scrollback), erase::SCROLLBACK);
+    assert_eq!(format!("{}", Line::Right), erase::LINE_RIGHT);
+    assert_eq!(format!("{}", Line::Left), erase::LINE_LEFT);
+    assert_eq!(format!("{}", Line::All), erase::LINE);
+    assert_eq!(format!("{}", Chars(12)), erase_chars!(12));
+    assert_eq!(erase::LINE, "\u{1B}[2K");
+}
+
+/// Check mode sequences are consistent with the mode numbers
+#[test]
+fn modes() {
+    use term_ctrl::modes::Mode;
+
+    for &mode in Mode::ALL.iter() {
+        assert_eq!(mode.set(), format!("\u{1B}[?{}h", mode.code()));
+        assert_eq!(mode.reset(), format!("\u{1B}[?{}l", mode.code()));
+    }
+    assert_eq!(Mode::BracketedPaste.code(), 2004);
+    assert_eq!(predefined::modes::HIDE_CURSOR, "\u{1B}[?25l");
+}
+
+/// Check hyperlink sequences
+#[test]
+fn hyperlink() {
+    use term_ctrl::hyperlink::Hyperlink;
+    use term_ctrl::osc::Terminator;
+
+    let link = Hyperlink::new("http://example.com", "text").enabled(true);
+    assert_eq!(format!("{}", link), "\u{1B}]8;;http://example.com\u{1B}\\text\u{1B}]8;;\u{1B}\\");
+    assert_eq!(format!("{}", link.enabled(false)), "text");
+    assert_eq!(format!("{}", link.id("a:b;c").terminator(Terminator::Bel)),
+        "\u{1B}]8;id=a%3Ab%3Bc;http://example.com\u{7}text\u{1B}]8;;\u{7}");
+
+    // Invalid URI bytes are escaped
+    let link = Hyperlink::new("file:///tmp/my file\u{7}é.txt", "f").enabled(true);
+    assert_eq!(format!("{}", link),
+        "\u{1B}]8;;file:///tmp/my%20file%07%C3%A9.txt\u{1B}\\f\u{1B}]8;;\u{1B}\\");
+}
+
+/// Check title sequences
+#[test]
+fn title() {
+    use term_ctrl::osc::Terminator;
+    use term_ctrl::title::{Title, Target};
+
+    assert_eq!(format!("{}", Title::new("abc")), "\u{1B}]0;abc\u{1B}\\");
+    assert_eq!(format!("{}", Title::new("abc").target(Target::IconName)),
+        "\u{1B}]1;abc\u{1B}\\");
+    assert_eq!(format!("{}", Title::new("abc").target(Target::Window).terminator(Terminator::Bel)),
+        "\u{1B}]2;abc\u{7}");
+    // Control chars are dropped
+    assert_eq!(format!("{}", Title::new("a\u{1B}]0;x\u{7}b\u{9C}c\td")),
+        "\u{1B}]0;a]0;xbcd\u{1B}\\");
+}
+
+/// Check clipboard sequences
+#[test]
+fn clipboard() {
+    use term_ctrl::clipboard::{Set, Query, Selection, Error, DEFAULT_LIMIT};
+    use term_ctrl::osc::Terminator;
+
+    assert_eq!(format!("{}", Set::new(b"").unwrap()), "\u{1B}]52;c;\u{1B}\\");
+    assert_eq!(format!("{}", Set::new(b"f").unwrap()), "\u{1B}]52;c;Zg==\u{1B}\\");
+    assert_eq!(format!("{}", Set::new(b"fo").unwrap().selection(Selection::Primary)),
+        "\u{1B}]52;p;Zm8=\u{1B}\\");
+    assert_eq!(format!("{}", Set::new(b"foo").unwrap().terminator(Terminator::Bel)),
+        "\u{1B}]52;c;Zm9v\u{7}");
+    assert_eq!(format!("{}", Set::new(&[0xFB, 0xFF, 0x00, 0x3E]).unwrap()),
+        "\u{1B}]52;c;+/8APg==\u{1B}\\");
+
+    // Size limit
+    assert!(Set::with_limit(b"foo", 4).is_ok());
+    assert_eq!(Set::with_limit(b"foob", 4), Err(Error::TooLarge));
+    assert!(Set::new(&[0; DEFAULT_LIMIT]).is_err());
+
+    let query = Query { selection: Selection::Select, ..Query::default() };
+    assert_eq!(format!("{}", query), "\u{1B}]52;s;?\u{1B}\\");
+}
+
+/// Check parsing of clipboard query replies
+#[cfg(feature = "alloc")]
+#[test]
+fn clipboard_reply() {
+    use term_ctrl::clipboard::{parse_reply, Selection, Error};
+
+    assert_eq!(parse_reply("\u{1B}]52;c;Zm9vYg==\u{7}"),
+        Ok((Selection::Clipboard, b"foob".to_vec())));
+    assert_eq!(parse_reply("\u{1B}]52;p;Zm9vYmE=\u{1B}\\"),
+        Ok((Selection::Primary, b"fooba".to_vec())));
+    assert_eq!(parse_reply("\u{1B}]52;s0;+/8APg==\u{7}"),
+        Ok((Selection::Select, vec![0xFB, 0xFF, 0, 0x3E])));
+    assert_eq!(parse_reply("\u{1B}]52;c;\u{7}"), Ok((Selection::Clipboard, vec![])));
+    assert_eq!(parse_reply("\u{1B}]52;c;Zm9v"), Err(Error::NotReply));
+    assert_eq!(parse_reply("\u{1B}]53;c;Zm9v\u{7}"), Err(Error::NotReply));
+    assert_eq!(parse_reply("\u{1B}]52;c;Zm9\u{7}"), Err(Error::InvalidData));
+    assert_eq!(parse_reply("\u{1B}]52;c;Zm=v\u{7}"), Err(Error::InvalidData));
+    assert_eq!(parse_reply("\u{1B}]52;c;Z!9v\u{7}"), Err(Error::InvalidData));
+}
+
+/// Check palette sequences
+#[test]
+fn palette() {
+    use term_ctrl::osc::Terminator;
+    use term_ctrl::palette::{Set, Query, Reset, Target};
+
+    assert_eq!(format!("{}", Set::new(Target::Index(196), (0xFF, 0x0A, 0))),
+        "\u{1B}]4;196;rgb:ff/0a/00\u{1B}\\");
+    assert_eq!(format!("{}", Set::new(Target::Foreground, (1, 2, 3)).terminator(Terminator::Bel)),
+        "\u{1B}]10;rgb:01/02/03\u{7}");
+    assert_eq!(format!("{}", Query::new(Target::Index(3))), "\u{1B}]4;3;?\u{1B}\\");
+    assert_eq!(format!("{}", Query::new(Target::Foreground)), "\u{1B}]10;?\u{1B}\\");
+    assert_eq!(format!("{}", Reset::new(Target::Index(3))), "\u{1B}]104;3\u{1B}\\");
+    assert_eq!(format!("{}", Reset::new(Target::Background)), "\u{1B}]111\u{1B}\\");
+}
+
+/// Check parsing of palette and default colour query replies
+#[test]
+fn palette_reply() {
+    use term_ctrl::palette::{parse_reply, parse_rgb, Target, Error};
+
+    assert_eq!(parse_rgb("rgb:ffff/8080/0000"), Some((0xFF, 0x80, 0)));
+    assert_eq!(parse_rgb("rgb:f/8/0"), Some((0xFF, 0x88, 0)));
+    assert_eq!(parse_rgb("rgb:fff/800/000"), Some((0xFF, 0x80, 0)));
+    assert_eq!(parse_rgb("rgb:1e/1E/2e"), Some((0x1E, 0x1E, 0x2E)));
+    assert_eq!(parse_rgb("rgb:ffff/ffff"), None);
+    assert_eq!(parse_rgb("rgb:ffff/ffff/ffff/ffff"), None);
+    assert_eq!(parse_rgb("rgb:fffff/0/0"), None);
+    assert_eq!(parse_rgb("rgb:/0/0"), None);
+    assert_eq!(parse_rgb("rgb:+f/0/0"), None);
+    assert_eq!(parse_rgb("#ffffff"), None);
+
+    assert_eq!(parse_reply("\u{1B}]11;rgb:0000/0000/0000\u{1B}\\"),
+        Ok((Target::Background, (0, 0, 0))));
+    assert_eq!(parse_reply("\u{1B}]10;rgb:ffff/ffff/ffff\u{7}"),
+        Ok((Target::Foreground, (0xFF, 0xFF, 0xFF))));
+    assert_eq!(parse_reply("\u{1B}]4;42;rgb:0000/8787/d7d7\u{7}"),
+        Ok((Target::Index(42), (0, 0x87, 0xD7))));
+    assert_eq!(parse_reply("\u{1B}]11;rgb:0000/0000/0000"), Err(Error::NotReply));
+    assert_eq!(parse_reply("\u{1B}]12;rgb:0000/0000/0000\u{7}"), Err(Error::NotReply));
+    assert_eq!(parse_reply("\u{1B}]4;256;rgb:0000/0000/0000\u{7}"), Err(Error::NotReply));
+    assert_eq!(parse_reply("\u{1B}]11;?\u{7}"), Err(Error::InvalidColour));
+}
+
+/// Check background detection and adaptive colours
+#[test]
+fn background() {
+    use term_ctrl::background::{Adaptive, Background};
+    use term_ctrl::colour::{BasicColour, Colour};
+
+    assert_eq!(Background::from_rgb((0xFF, 0xFF, 0xFF)), Background::Light);
+    assert_eq!(Background::from_rgb((0xFD, 0xF6, 0xE3)), Background::Light);
+    assert_eq!(Background::from_rgb((0, 0, 0)), Background::Dark);
+    assert_eq!(Background::from_rgb((0x00, 0x2B, 0x36)), Background::Dark);
+    assert_eq!(Background::from_rgb((0, 0, 0xFF)), Background::Dark);
+
+    assert_eq!(Background::from_reply("\u{1B}]11;rgb:ffff/ffff/ffff\u{7}"), Background::Light);
+    assert_eq!(Background::from_reply("\u{1B}]11;rgb:1e1e/1e1e/2e2e\u{7}"), Background::Dark);
+    // The foreground colour is not the background colour!
+    assert_eq!(Background::from_reply("\u{1B}]10;rgb:ffff/ffff/ffff\u{7}"), Background::Unknown);
+    assert_eq!(Background::from_reply("garbage"), Background::Unknown);
+
+    assert_eq!(Background::from_colorfgbg("15;0"), Background::Dark);
+    assert_eq!(Background::from_colorfgbg("0;15"), Background::Light);
+    assert_eq!(Background::from_colorfgbg("0;default;7"), Background::Light);
+    assert_eq!(Background::from_colorfgbg("7;8"), Background::Dark);
+    assert_eq!(Background::from_colorfgbg("15;default"), Background::Unknown);
+    assert_eq!(Background::from_colorfgbg(""), Background::Unknown);
+
+    let env = [("COLORFGBG", "0;15")];
+    assert_eq!(Background::from_env(|n| env.iter().find(|e| e.0 == n).map(|e| e.1)),
+        Background::Light);
+    assert_eq!(Background::from_env(|_| None::<&str>), Background::Unknown);
+
+    let warning = Adaptive::new(Colour::Indexed(130), BasicColour::Yellow.into());
+    assert_eq!(warning.resolve(Background::Light), Colour::Indexed(130));
+    assert_eq!(warning.resolve(Background::Dark), Colour::Basic(BasicColour::Yellow));
+    assert_eq!(warning.resolve(Background::Unknown), Colour::Basic(BasicColour::Yellow));
+    assert_eq!(format!("{}", warning.fg(Background::Light)), "38;5;130");
+    assert_eq!(format!("{}", warning.bg(Background::Dark)), "43");
+}
+
+/// Check terminal size from the environment
+#[test]
+fn terminal_size_env() {
+    use term_ctrl::support::{terminal_size_from_env, TerminalSize};
+
+    let env = [("COLUMNS", "120"), ("LINES", " 40\n")];
+    let lookup = |name: &str| env.iter().find(|e| e.0 == name).map(|e| e.1);
+    assert_eq!(terminal_size_from_env(lookup),
+        Some(TerminalSize { cols: 120, rows: 40, width: 0, height: 0 }));
+
+    let env = [("COLUMNS", "120"), ("LINES", "0")];
+    assert_eq!(terminal_size_from_env(|n| env.iter().find(|e| e.0 == n).map(|e| e.1)), None);
+    let env = [("COLUMNS", "wide"), ("LINES", "40")];
+    assert_eq!(terminal_size_from_env(|n| env.iter().find(|e| e.0 == n).map(|e| e.1)), None);
+    assert_eq!(terminal_size_from_env(|_| None::<&str>), None);
+}
+
+/// Check decoding of input
+#[cfg(feature = "alloc")]
+#[test]
+fn input() {
+    use term_ctrl::input::*;
+
+    fn events(bytes: &[u8]) -> Vec<Event> {
+        let mut parser = Parser::new();
+        parser.feed(bytes);
+        parser.collect()
+    }
+    let k = |key, modifiers| Event::Key(KeyEvent::new(key, modifiers));
+    let none = Modifiers::NONE;
+
+    // Plain chars and control chars
+    assert_eq!(events(b"a\r\t\x7F\x01\x1A\x00"), vec![
+        k(Key::Char('a'), none), k(Key::Enter, none), k(Key::Tab, none), k(Key::Backspace, none),
+        k(Key::Char('a'), Modifiers::CTRL), k(Key::Char('z'), Modifiers::CTRL),
+        k(Key::Char(' '), Modifiers::CTRL),
+    ]);
+    assert_eq!(events("é€😀".as_bytes()), vec![
+        k(Key::Char('é'), none), k(Key::Char('€'), none), k(Key::Char('😀'), none),
+    ]);
+    assert_eq!(events(b"\xFFa"), vec![Event::Unknown(vec![0xFF]), k(Key::Char('a'), none)]);
+
+    // Alt
+    assert_eq!(events(b"\x1Bx\x1B\x01"), vec![
+        k(Key::Char('x'), Modifiers::ALT), k(Key::Char('a'), Modifiers::CTRL | Modifiers::ALT),
+    ]);
+
+    // CSI and SS3 keys
+    assert_eq!(events(b"\x1B[A\x1B[1;5B\x1B[1;2C\x1B[D\x1B[H\x1B[F\x1B[Z"), vec![
+        k(Key::Up, none), k(Key::Down, Modifiers::CTRL), k(Key::Right, Modifiers::SHIFT),
+        k(Key::Left, none), k(Key::Home, none), k(Key::End, none), k(Key::Tab, Modifiers::SHIFT),
+    ]);
+    assert_eq!(events(b"\x1BOP\x1BOS\x1BOA\x1B[1;3Q"), vec![
+        k(Key::F(1), none), k(Key::F(4), none), k(Key::Up, none), k(Key::F(2), Modifiers::ALT),
+    ]);
+    assert_eq!(events(b"\x1B[2~\x1B[3;5~\x1B[5~\x1B[6~\x1B[15~\x1B[17~\x1B[24;2~"), vec![
+        k(Key::Insert, none), k(Key::Delete, Modifiers::CTRL), k(Key::PageUp, none),
+        k(Key::PageDown, none), k(Key::F(5), none), k(Key::F(6), none),
+        k(Key::F(12), Modifiers::SHIFT),
+    ]);
+    assert_eq!(events(b"\x1B[99~\x1B[?1u"),
+        vec![Event::Unknown(b"\x1B[99~".to_vec()), Event::Unknown(b"\x1B[?1u".to_vec())]);
+
+    // Focus
+    assert_eq!(events(b"\x1B[I\x1B[O"), vec![Event::FocusIn, Event::FocusOut]);
+
+    // Mouse
+    let m = |kind, col, row, modifiers| Event::Mouse(MouseEvent { kind, row, col, modifiers });
+    assert_eq!(events(b"\x1B[<0;10;5M\x1B[<0;10;5m\x1B[<2;1;1M\x1B[<18;3;4M"), vec![
+        m(MouseKind::Press(MouseButton::Left), 10, 5, none),
+        m(MouseKind::Release(MouseButton::Left), 10, 5, none),
+        m(MouseKind::Press(MouseButton::Right), 1, 1, none),
+        m(MouseKind::Press(MouseButton::Right), 3, 4, Modifiers::CTRL),
+    ]);
+    assert_eq!(events(b"\x1B[<32;2;3M\x1B[<35;4;5M\x1B[<64;1;1M\x1B[<69;1;1M"), vec![
+        m(MouseKind::Drag(MouseButton::Left), 2, 3, none),
+        m(MouseKind::Move, 4, 5, none),
+        m(MouseKind::ScrollUp, 1, 1, none),
+        m(MouseKind::ScrollDown, 1, 1, Modifiers::SHIFT),
+    ]);
+    assert_eq!(events(b"\x1B[<0;1M"), vec![Event::Unknown(b"\x1B[<0;1M".to_vec())]);
+
+    // Bracketed paste
+    assert_eq!(events(b"\x1B[200~hi \x1B[A\x1B[201~x"),
+        vec![Event::Paste("hi \x1B[A".into()), k(Key::Char('x'), none)]);
+}
+
+/// Check incremental decoding of input, and flushing
+#[cfg(feature = "alloc")]
+#[test]
+fn input_incremental() {
+    use term_ctrl::input::*;
+
+    let k = |key, modifiers| Some(Event::Key(KeyEvent::new(key, modifiers)));
+    let mut parser = Parser::new();
+
+    // A sequence split across reads
+    for &b in b"\x1B[1;5" {
+        parser.feed(&[b]);
+        assert_eq!(parser.next(), None);
+    }
+    parser.feed(b"A");
+    assert_eq!(parser.next(), k(Key::Up, Modifiers::CTRL));
+
+    // A char split across reads
+    parser.feed(&"€".as_bytes()[..2]);
+    assert_eq!(parser.next(), None);
+    parser.feed(&"€".as_bytes()[2..]);
+    assert_eq!(parser.next(), k(Key::Char('€'), Modifiers::NONE));
+
+    // Lone escape
+    parser.feed(b"\x1B");
+    assert_eq!(parser.next(), None);
+    assert_eq!(parser.flush(), k(Key::Escape, Modifiers::NONE));
+    assert_eq!(parser.flush(), None);
+    parser.feed(b"\x1B\x1B");
+    assert_eq!(parser.next(), k(Key::Escape, Modifiers::NONE));
+    assert_eq!(parser.next(), None);
+    assert_eq!(parser.flush(), k(Key::Escape, Modifiers::NONE));
+    parser.feed(b"\x1B[");
+    assert_eq!(parser.flush(), k(Key::Char('['), Modifiers::ALT));
+    parser.feed(b"a\x1B[1;");
+    assert_eq!(parser.flush(), k(Key::Char('a'), Modifiers::NONE));
+    assert_eq!(parser.flush(), Some(Event::Unknown(b"\x1B[1;".to_vec())));
+
+    // Paste split across reads, including the end sequence
+    parser.feed(b"\x1B[200~abc\x1B[20");
+    assert_eq!(parser.next(), None);
+    assert_eq!(parser.flush(), None);
+    parser.feed(b"1~");
+    assert_eq!(parser.next(), Some(Event::Paste("abc".into())));
+    assert_eq!(parser.next(), None);
+}
+
+/// Check kitty keyboard protocol sequences
+#[test]
+fn keyboard() {
+    use term_ctrl::keyboard::{Flags, Push, Pop, QUERY, parse_flags_reply};
+
+    let flags = Flags::DISAMBIGUATE | Flags::REPORT_EVENT_TYPES | Flags::REPORT_TEXT;
+    assert_eq!(flags.bits(), 19);
+    assert!(flags.contains(Flags::REPORT_EVENT_TYPES));
+    assert!(!flags.contains(Flags::REPORT_ALL_KEYS));
+    assert_eq!(format!("{}", Push(flags)), "\u{1B}[>19u");
+    assert_eq!(format!("{}", Pop(2)), "\u{1B}[<2u");
+    assert_eq!(QUERY, "\u{1B}[?u");
+
+    assert_eq!(parse_flags_reply("\u{1B}[?0u"), Some(Flags::NONE));
+    assert_eq!(parse_flags_reply("\u{1B}[?19u"), Some(flags));
+    assert_eq!(parse_flags_reply("\u{1B}[?u"), None);
+    assert_eq!(parse_flags_reply("\u{1B}[?1;2u"), None);
+    assert_eq!(parse_flags_reply("\u{1B}[1u"), None);
+}
+
+/// Check decoding of kitty keyboard protocol key reports
+#[test]
+fn keyboard_reports() {
+    use term_ctrl::input::{Key, KeyKind, Modifiers};
+    use term_ctrl::keyboard::{parse_report, KeyReport};
+
+    assert_eq!(parse_report("\u{1B}[97u"), Some(KeyReport {
+        code: 97, shifted: None, base: None, modifiers: Modifiers::NONE, kind: KeyKind::Press,
+    }));
+    assert_eq!(parse_report("\u{1B}[97:65;2u"), Some(KeyReport {
+        code: 97, shifted: Some(65), base: None, modifiers: Modifiers::SHIFT, kind: KeyKind::Press,
+    }));
+    assert_eq!(parse_report("\u{1B}[1089::99;5:3u"), Some(KeyReport {
+        code: 1089, shifted: None, base: Some(99), modifiers: Modifiers::CTRL,
+        kind: KeyKind::Release,
+    }));
+    assert_eq!(parse_report("\u{1B}[97;41:2;97u").map(|r| (r.modifiers, r.kind)),
+        Some((Modifiers::META | Modifiers::SUPER, KeyKind::Repeat)));
+    assert_eq!(parse_report("\u{1B}[97;1:4u"), None);
+    assert_eq!(parse_report("\u{1B}[97;0u"), None);
+    assert_eq!(parse_report("\u{1B}[u"), None);
+
+    assert_eq!(parse_report("\u{1B}[9u").and_then(|r| r.key()), Some(Key::Tab));
+    assert_eq!(parse_report("\u{1B}[13u").and_then(|r| r.key()), Some(Key::Enter));
+    assert_eq!(parse_report("\u{1B}[27u").and_then(|r| r.key()), Some(Key::Escape));
+    assert_eq!(parse_report("\u{1B}[127u").and_then(|r| r.key()), Some(Key::Backspace));
+    assert_eq!(parse_report("\u{1B}[57376u").and_then(|r| r.key()), Some(Key::F(13)));
+    assert_eq!(parse_report("\u{1B}[57441u").and_then(|r| r.key()), None);
+}
+
+/// Check decoding of kitty keyboard protocol key reports through the input parser
+#[cfg(feature = "alloc")]
+#[test]
+fn keyboard_reports_input() {
+    use term_ctrl::input::{Event, Key, KeyEvent, KeyKind, Modifiers, Parser};
+
+    // Ctrl+I can be told from Tab, and releases seen
+    let mut parser = Parser::new();
+    parser.feed(b"\x1B[105;5u\t\x1B[105;5:3u\x1B[1;1:3A\x1B[3;5:2~\x1B[57441u");
+    let k = |key, modifiers, kind| Event::Key(KeyEvent { key, modifiers, kind });
+    assert_eq!(parser.collect::<Vec<_>>(), vec![
+        k(Key::Char('i'), Modifiers::CTRL, KeyKind::Press),
+        k(Key::Tab, Modifiers::NONE, KeyKind::Press),
+        k(Key::Char('i'), Modifiers::CTRL, KeyKind::Release),
+        k(Key::Up, Modifiers::NONE, KeyKind::Release),
+        k(Key::Delete, Modifiers::CTRL, KeyKind::Repeat),
+        Event::Unknown(b"\x1B[57441u".to_vec()),
+    ]);
+}
+
+/// Check capability query sequences and reply parsing
+#[test]
+fn query() {
+    use term_ctrl::modes::Mode;
+    use term_ctrl::query::*;
+
+    assert_eq!(DA1, "\u{1B}[c");
+    assert_eq!(DA2, "\u{1B}[>c");
+    assert_eq!(XTVERSION, "\u{1B}[>0q");
+    assert_eq!(format!("{}", RequestMode(2004)), "\u{1B}[?2004$p");
+    assert_eq!(format!("{}", RequestMode::from(Mode::AltScreen)), "\u{1B}[?1049$p");
+
+    let da1 = parse_da1_reply("\u{1B}[?62;4;22;52c").unwrap();
+    assert_eq!(da1.level, 62);
+    assert!(da1.has_feature(4) && da1.has_feature(22) && da1.has_feature(52));
+    assert!(!da1.has_feature(1) && !da1.has_feature(64));
+    assert_eq!(parse_da1_reply("\u{1B}[?1;2c"), Some(PrimaryAttributes { level: 1, features: 4 }));
+    assert_eq!(parse_da1_reply("\u{1B}[?6c"), Some(PrimaryAttributes { level: 6, features: 0 }));
+    assert_eq!(parse_da1_reply("\u{1B}[?c"), None);
+    assert_eq!(parse_da1_reply("\u{1B}[?62;x;22c"), None);
+    assert_eq!(parse_da1_reply("\u{1B}[62;22c"), None);
+
+    assert_eq!(parse_da2_reply("\u{1B}[>41;388;0c"),
+        Some(SecondaryAttributes { terminal: 41, version: 388, rom: 0 }));
+    assert_eq!(parse_da2_reply("\u{1B}[>1;4000c"),
+        Some(SecondaryAttributes { terminal: 1, version: 4000, rom: 0 }));
+    assert_eq!(parse_da2_reply("\u{1B}[>1c"), None);
+    assert_eq!(parse_da2_reply("\u{1B}[>1;2;3;4c"), None);
+    assert_eq!(parse_da2_reply("\u{1B}[?62;22c"), None);
+
+    assert_eq!(parse_xtversion_reply("\u{1B}P>|XTerm(388)\u{1B}\\"), Some("XTerm(388)"));
+    assert_eq!(parse_xtversion_reply("\u{1B}P>|kitty(0.31.0)\u{7}"), Some("kitty(0.31.0)"));
+    assert_eq!(parse_xtversion_reply("\u{1B}P>|XTerm(388)"), None);
+    assert_eq!(parse_xtversion_reply("\u{1B}P>|a\u{1B}b\u{1B}\\"), None);
+
+    assert_eq!(parse_mode_reply("\u{1B}[?2026;2$y"), Some((2026, ModeState::Reset)));
+    assert_eq!(parse_mode_reply("\u{1B}[?25;1$y"), Some((25, ModeState::Set)));
+    assert_eq!(parse_mode_reply("\u{1B}[?9999;0$y"), Some((9999, ModeState::NotRecognised)));
+    assert_eq!(parse_mode_reply("\u{1B}[?7;5$y"), None);
+    assert_eq!(parse_mode_reply("\u{1B}[?7$y"), None);
+    assert!(ModeState::PermanentlySet.is_set() && !ModeState::PermanentlyReset.is_set());
+    assert!(ModeState::Reset.is_supported() && !ModeState::NotRecognised.is_supported());
+}
+
+/// Check parsing of cursor position reports
+#[test]
+fn cursor_position_report() {
+    use term_ctrl::cursor::{find_position_report, parse_position_report, Position};
+    use term_ctrl::predefined::cursor::REPORT_POSITION;
+
+    assert_eq!(REPORT_POSITION, "\u{1B}[6n");
+    assert_eq!(parse_position_report(b"\x1B[1;1R"), Some(Position::new(1, 1)));
+    assert_eq!(parse_position_report(b"\x1B[24;80R"), Some(Position::new(24, 80)));
+    assert_eq!(parse_position_report(b"\x1B[24;80"), None);
+    assert_eq!(parse_position_report(b"\x1B[24R"), None);
+    assert_eq!(parse_position_report(b"\x1B[;80R"), None);
+    assert_eq!(parse_position_report(b"\x1B[24;80Rx"), None);
+    assert_eq!(parse_position_report(b"\x1B[99999;1R"), None);
+
+    // Other input around and between
+    assert_eq!(find_position_report(b"\x1B[A\x1B[5;6Rq"), Some((Position::new(5, 6), 3..9)));
+    assert_eq!(find_position_report(b"\x1B[\x1B[5;6R"), Some((Position::new(5, 6), 2..8)));
+    assert_eq!(find_position_report(b"\x1B[1;5A\x1B[1;"), None);
+    assert_eq!(find_position_report(b""), None);
+}
+
 /// Check a few `predefined` sequences seem to be available and as expected
 #[test]
 fn predefines() {
@@ -29,23 +509,780 @@ fn predefines() {
     assert_eq!(predefined::combinations::fg_bold::GREEN, "\u{1B}[32;1m");
 }
 
+/// Check `Style` writes the expected sequences
+#[test]
+fn style() {
+    use term_ctrl::colour::{Colour, BasicColour};
+    use term_ctrl::style::{Style, Effects, Font};
+
+    assert_eq!(format!("{}", Style::new()), "");
+    assert_eq!(format!("{}", Style::new().bold()), predefined::effects::BOLD);
+    assert_eq!(format!("{}", Style::new().fg(BasicColour::Green)), predefined::colours::fg::GREEN);
+    assert_eq!(format!("{}", Style::new().bg(Colour::Bright(BasicColour::White))),
+        predefined::colours::bg::bright::WHITE);
+    assert_eq!(format!("{}", Style::new().fg(BasicColour::Green).bold()),
+        seq!(1, 32));
+    assert_eq!(format!("{}", Style::new().fg(Colour::Default).bg(Colour::Default)),
+        predefined::colours::RESET);
+
+    let style = Style::new()
+        .effects(Effects::STRIKE | Effects::FRAMED | Effects::IDEOGRAM_STRESS_MARKING)
+        .font(Font::Alt3)
+        .italic();
+    assert_eq!(format!("{}", style), seq!(3, 9, 51, 64, 13));
+    assert!(style.effects.contains(Effects::ITALIC | Effects::STRIKE));
+    assert!(!style.effects.contains(Effects::BOLD));
+    assert!(!style.is_plain());
+
+    let style = Style::new().fg(Colour::Rgb(1, 2, 3)).bg(Colour::Indexed(4)).dim();
+    assert_eq!(format!("{}", style), seq!(2, rgb_fg!(1, 2, 3), c256_bg!(4)));
+}
+
+/// Check `Colour` code sets match the equivalent constants and macros
+#[test]
+fn colour_codes() {
+    use term_ctrl::colour::{Colour, BasicColour};
+
+    assert_eq!(format!("{}", Colour::Basic(BasicColour::Black).fg()), codes::colours::fg::BLACK);
+    assert_eq!(format!("{}", Colour::Basic(BasicColour::White).bg()), codes::colours::bg::WHITE);
+    assert_eq!(format!("{}", Colour::Bright(BasicColour::Cyan).fg()),
+        codes::colours::fg::bright::CYAN);
+    assert_eq!(format!("{}", Colour::Bright(BasicColour::Yellow).bg()),
+        codes::colours::bg::bright::YELLOW);
+    assert_eq!(format!("{}", Colour::Indexed(0).fg()), c256_fg!(0));
+    assert_eq!(format!("{}", Colour::Indexed(255).bg()), c256_bg!(255));
+    assert_eq!(format!("{}", Colour::Rgb(180, 15, 70).fg()), rgb_fg!(180, 15, 70));
+    assert_eq!(format!("{}", Colour::Rgb(0, 255, 1).bg()), rgb_bg!(0, 255, 1));
+    assert_eq!(format!("{}", Colour::Default.fg()), codes::colours::fg::RESET);
+    assert_eq!(format!("{}", Colour::Default.bg()), codes::colours::bg::RESET);
+}
+
+/// Check colour downgrading
+#[test]
+fn colour_downgrade() {
+    use term_ctrl::colour::{self, Colour, BasicColour, ColourLevel};
+    use term_ctrl::style::Style;
+
+    assert!(ColourLevel::None < ColourLevel::Ansi16);
+    assert!(ColourLevel::Ansi256 < ColourLevel::TrueColour);
+
+    assert_eq!(colour::indexed_to_rgb(1), (205, 0, 0));
+    assert_eq!(colour::indexed_to_rgb(16), (0, 0, 0));
+    assert_eq!(colour::indexed_to_rgb(196), (255, 0, 0));
+    assert_eq!(colour::indexed_to_rgb(231), (255, 255, 255));
+    assert_eq!(colour::indexed_to_rgb(232), (8, 8, 8));
+    assert_eq!(colour::indexed_to_rgb(255), (238, 238, 238));
+
+    // Exact cube and grey entries map back to themselves
+    for n in 16..=255 {
+        let (r, g, b) = colour::indexed_to_rgb(n);
+        assert_eq!(colour::rgb_to_256(r, g, b), n);
+    }
+    assert_eq!(colour::rgb_to_256(250, 5, 5), 196);
+    assert_eq!(colour::rgb_to_256(128, 128, 130), 244);
+
+    assert_eq!(colour::rgb_to_16(0, 0, 0), Colour::Basic(BasicColour::Black));
+    assert_eq!(colour::rgb_to_16(250, 250, 250), Colour::Bright(BasicColour::White));
+    assert_eq!(colour::rgb_to_16(10, 190, 200), Colour::Basic(BasicColour::Cyan));
+    assert_eq!(colour::rgb_to_16(240, 30, 20), Colour::Bright(BasicColour::Red));
+
+    let basic = Colour::Basic(BasicColour::Blue);
+    assert_eq!(basic.downgrade(ColourLevel::Ansi16), Some(basic));
+    assert_eq!(Colour::Default.downgrade(ColourLevel::Ansi16), Some(Colour::Default));
+    assert_eq!(Colour::Indexed(9).downgrade(ColourLevel::Ansi16),
+        Some(Colour::Bright(BasicColour::Red)));
+    assert_eq!(Colour::Indexed(28).downgrade(ColourLevel::Ansi16),
+        Some(Colour::Basic(BasicColour::Green)));
+    assert_eq!(Colour::Indexed(28).downgrade(ColourLevel::Ansi256), Some(Colour::Indexed(28)));
+    assert_eq!(Colour::Rgb(0, 135, 0).downgrade(ColourLevel::Ansi256), Some(Colour::Indexed(28)));
+    assert_eq!(basic.downgrade(ColourLevel::None), None);
+
+    let style = Style::new().fg(Colour::Rgb(255, 0, 0)).bg(Colour::Indexed(21)).underline();
+    assert_eq!(format!("{}", style.downgrade(ColourLevel::TrueColour)), format!("{}", style));
+    assert_eq!(format!("{}", style.downgrade(ColourLevel::Ansi256)),
+        seq!(4, c256_fg!(196), c256_bg!(21)));
+    assert_eq!(format!("{}", style.downgrade(ColourLevel::Ansi16)), seq!(4, 91, 44));
+    assert!(style.downgrade(ColourLevel::None).is_plain());
+}
+
+/// Check parsing of sequences back into attribute changes
+#[test]
+fn parse_sgr() {
+    use term_ctrl::colour::{Colour, BasicColour};
+    use term_ctrl::parse::{self, Attribute, Error};
+    use term_ctrl::style::Font;
+
+    fn all(seq: &str) -> Vec<Result<Attribute, Error>> {
+        parse::sgr(seq).unwrap().collect()
+    }
+
+    assert_eq!(all(predefined::RESET), [Ok(Attribute::Reset)]);
+    assert_eq!(all("\u{1B}[m"), [Ok(Attribute::Reset)]);
+    assert_eq!(all(seq!(1, "", 22)),
+        [Ok(Attribute::Bold), Ok(Attribute::Reset), Ok(Attribute::ResetIntensity)]);
+    assert_eq!(all(seq!(13, 26, 65)), [
+        Ok(Attribute::Font(Font::Alt3)),
+        Ok(Attribute::Unknown(26)),
+        Ok(Attribute::ResetIdeogram),
+    ]);
+    assert_eq!(all(predefined::colours::RESET), [Ok(Attribute::ResetFg), Ok(Attribute::ResetBg)]);
+    assert_eq!(all(seq!(33, 104)), [
+        Ok(Attribute::SetFg(Colour::Basic(BasicColour::Yellow))),
+        Ok(Attribute::SetBg(Colour::Bright(BasicColour::Blue))),
+    ]);
+    assert_eq!(all(seq!(c256_fg!(238), rgb_bg!(180, 15, 70), 4)), [
+        Ok(Attribute::SetFg(Colour::Indexed(238))),
+        Ok(Attribute::SetBg(Colour::Rgb(180, 15, 70))),
+        Ok(Attribute::Underline),
+    ]);
+
+    // Colon separated forms, with and without colour-space ID
+    assert_eq!(all("\u{1B}[38:5:100;48:2::1:2:3;38:2:4:5:6m"), [
+        Ok(Attribute::SetFg(Colour::Indexed(100))),
+        Ok(Attribute::SetBg(Colour::Rgb(1, 2, 3))),
+        Ok(Attribute::SetFg(Colour::Rgb(4, 5, 6))),
+    ]);
+
+    // Malformed or truncated
+    assert_eq!(parse::sgr("\u{1B}[1").unwrap_err(), Error::NotSgr);
+    assert_eq!(parse::sgr("[1m").unwrap_err(), Error::NotSgr);
+    assert_eq!(parse::sgr("\u{1B}m").unwrap_err(), Error::NotSgr);
+    assert_eq!(all("\u{1B}[1;x;3m"), [Ok(Attribute::Bold), Err(Error::InvalidCode)]);
+    assert_eq!(all("\u{1B}[38;5m"), [Err(Error::Truncated)]);
+    assert_eq!(all("\u{1B}[48;2;1;2m"), [Err(Error::Truncated)]);
+    assert_eq!(all("\u{1B}[38;2;1;256;3;1m"), [Err(Error::InvalidColour)]);
+    assert_eq!(all("\u{1B}[38;7;1m"), [Err(Error::InvalidColour)]);
+}
+
+/// Check underline styles and colour, through macros, constants, styles and parsing
+#[test]
+fn underline() {
+    use term_ctrl::colour::{Colour, BasicColour, ColourLevel};
+    use term_ctrl::parse::{self, Attribute, Error};
+    use term_ctrl::style::{Style, Effects};
+
+    assert_eq!(c256_ul!(9), "58;5;9");
+    assert_eq!(rgb_ul!(1, 2, 3), "58;2;1;2;3");
+    assert_eq!(predefined::effects::CURLY_UNDERLINE, "\u{1B}[4:3m");
+    assert_eq!(predefined::effects::DASHED_UNDERLINE, "\u{1B}[4:5m");
+    assert_eq!(predefined::colours::RESET_UL, "\u{1B}[59m");
+
+    assert_eq!(format!("{}", Colour::Basic(BasicColour::Red).ul()), c256_ul!(1));
+    assert_eq!(format!("{}", Colour::Bright(BasicColour::Red).ul()), c256_ul!(9));
+    assert_eq!(format!("{}", Colour::Indexed(238).ul()), c256_ul!(238));
+    assert_eq!(format!("{}", Colour::Rgb(180, 15, 70).ul()), rgb_ul!(180, 15, 70));
+    assert_eq!(format!("{}", Colour::Default.ul()), "59");
+
+    let style = Style::new()
+        .effects(Effects::DOTTED_UNDERLINE | Effects::BOLD)
+        .fg(BasicColour::Green)
+        .ul(Colour::Rgb(255, 0, 0));
+    assert_eq!(format!("{}", style), seq!(1, "4:4", 32, rgb_ul!(255, 0, 0)));
+    assert_eq!(format!("{}", style.downgrade(ColourLevel::Ansi256)),
+        seq!(1, "4:4", 32, c256_ul!(196)));
+    assert_eq!(format!("{}", Style { effects: Effects::UNDERLINES, ..Style::new() }),
+        seq!(4, "4:3", "4:4", "4:5", 21));
+
+    fn all(seq: &str) -> Vec<Result<Attribute, Error>> {
+        parse::sgr(seq).unwrap().collect()
+    }
+    assert_eq!(all(seq!("4:0", "4:1", "4:2", "4:3", "4:4", "4:5", "4:9")), [
+        Ok(Attribute::ResetUnderline),
+        Ok(Attribute::Underline),
+        Ok(Attribute::DblUnderline),
+        Ok(Attribute::CurlyUnderline),
+        Ok(Attribute::DottedUnderline),
+        Ok(Attribute::DashedUnderline),
+        Ok(Attribute::UnknownUnderline(9)),
+    ]);
+    assert_eq!(all(seq!(c256_ul!(9), "58:2::1:2:3", 59)), [
+        Ok(Attribute::SetUl(Colour::Indexed(9))),
+        Ok(Attribute::SetUl(Colour::Rgb(1, 2, 3))),
+        Ok(Attribute::ResetUl),
+    ]);
+    assert_eq!(all("\u{1B}[58;5m"), [Err(Error::Truncated)]);
+    assert_eq!(all("\u{1B}[4:xm"), [Err(Error::InvalidCode)]);
+
+    // Round trip, with the reset clearing any underline style but not the colour
+    let mut applied = Style::new();
+    for attr in parse::sgr(&format!("{}", style)).unwrap() {
+        applied.apply(attr.unwrap());
+    }
+    assert_eq!(applied, style);
+    applied.apply(Attribute::ResetUnderline);
+    assert_eq!(applied, Style::new().bold().fg(BasicColour::Green).ul(Colour::Rgb(255, 0, 0)));
+    applied.apply(Attribute::ResetUl);
+    assert_eq!(applied.ul, None);
+
+    // Underline styles replace one another, as on the terminal
+    let mut applied = Style::new();
+    for seq in &[predefined::effects::CURLY_UNDERLINE, predefined::effects::UNDERLINE] {
+        for attr in parse::sgr(seq).unwrap() {
+            applied.apply(attr.unwrap());
+        }
+    }
+    assert_eq!(applied, Style::new().underline());
+    assert_eq!(format!("{}", applied), predefined::effects::UNDERLINE);
+    applied.apply(Attribute::DblUnderline);
+    applied.apply(Attribute::DashedUnderline);
+    assert_eq!(applied.effects, Effects::DASHED_UNDERLINE);
+
+    // Likewise when building a style
+    let built = Style::new().bold().effects(Effects::CURLY_UNDERLINE).underline();
+    assert_eq!(built.effects, Effects::BOLD | Effects::UNDERLINE);
+    assert_eq!(format!("{}", built), seq!(1, 4));
+    let built = Style::new().underline().effects(Effects::DOTTED_UNDERLINE | Effects::ITALIC);
+    assert_eq!(built.effects, Effects::DOTTED_UNDERLINE | Effects::ITALIC);
+    assert_eq!(Style::new().effects(Effects::UNDERLINE | Effects::CURLY_UNDERLINE).effects,
+        Effects::CURLY_UNDERLINE);
+    assert_eq!(Style::new().effects(Effects::UNDERLINES).effects, Effects::DASHED_UNDERLINE);
+}
+
+/// Check stripping of sequences
+#[cfg(feature = "alloc")]
+#[test]
+fn strip() {
+    use std::borrow::Cow;
+    use term_ctrl::strip::{strip, strip_bytes};
+
+    // Clean input passes through without allocation
+    match strip("Hello world!") {
+        Cow::Borrowed(s) => assert_eq!(s, "Hello world!"),
+        Cow::Owned(_) => panic!("allocated for clean input"),
+    }
+    match strip_bytes(b"Hi") {
+        Cow::Borrowed(b) => assert_eq!(b, b"Hi"),
+        Cow::Owned(_) => panic!("allocated for clean input"),
+    }
+
+    // SGR
+    assert_eq!(strip("\u{1B}[1;31mError:\u{1B}[0m oops"), "Error: oops");
+    // Other CSI, with private parameter and intermediate bytes
+    assert_eq!(strip("a\u{1B}[2Kb\u{1B}[?25lc\u{1B}[0 qd"), "abcd");
+    // OSC with each terminator
+    assert_eq!(strip("a\u{1B}]0;title\u{7}b\u{1B}]8;;http://x\u{1B}\\c"), "abc");
+    // Other string types, which do not end with BEL
+    assert_eq!(strip("a\u{1B}P1$r\u{7}q\u{1B}\\b\u{1B}_apc\u{1B}\\c"), "abc");
+    // Two byte and intermediate escapes
+    assert_eq!(strip("a\u{1B}7b\u{1B}(Bc\u{1B}8"), "abc");
+    // Non-ASCII text is preserved, including within removed sequences
+    assert_eq!(strip("é\u{1B}]2;ťitle\u{7}ü\u{1B}[1m✓"), "éü✓");
+    // Malformed sequences are abandoned without eating following text
+    assert_eq!(strip("a\u{1B}[1\nb\u{1B}[é"), "a\nbé");
+    // Cancelled sequence, and incomplete trailing sequence
+    assert_eq!(strip("a\u{1B}[1\u{18}b\u{1B}[3"), "ab");
+    assert_eq!(&*strip_bytes(b"\xFF\x1B[1m\xFE"), b"\xFF\xFE");
+}
+
+/// Check the stream writer, including handling of sequences split across writes
+#[cfg(feature = "std")]
+#[test]
+fn writer() {
+    use std::io::Write;
+    use term_ctrl::writer::Writer;
+
+    let mut out = Writer::new(Vec::new(), true);
+    assert!(out.is_stripping());
+    write!(out, "{}Error:{} oops", predefined::colours::fg::RED, predefined::RESET).unwrap();
+    out.write_all(b" \x1B[").unwrap();
+    out.write_all(b"1;3").unwrap();
+    out.write_all(b"1mred\x1B]0;ti").unwrap();
+    out.write_all(b"tle\x1B").unwrap();
+    out.write_all(b"\\!").unwrap();
+    assert_eq!(out.into_inner(), b"Error: oops red!");
+
+    let mut out = Writer::new(Vec::new(), false);
+    write!(out, "{}Error:{} oops", predefined::colours::fg::RED, predefined::RESET).unwrap();
+    assert_eq!(out.into_inner(), b"\x1B[31mError:\x1B[0m oops");
+}
+
+/// Check display width measurement and padding
+#[test]
+fn width() {
+    use term_ctrl::width::{char_width, visible_width, Padded};
+    use predefined::{RESET, effects::BOLD};
+
+    assert_eq!(char_width('a'), 1);
+    assert_eq!(char_width('\n'), 0);
+    assert_eq!(char_width('\u{301}'), 0); // Combining acute accent
+    assert_eq!(char_width('\u{200B}'), 0); // Zero width space
+    assert_eq!(char_width('漢'), 2);
+    assert_eq!(char_width('Ｗ'), 2); // Fullwidth
+    assert_eq!(char_width('😀'), 2);
+    assert_eq!(char_width('é'), 1);
+    // Unassigned, other than in the space reserved for CJK ideographs
+    assert_eq!(char_width('\u{378}'), 1);
+    assert_eq!(char_width('\u{50000}'), 1);
+    assert_eq!(char_width('\u{10FFFF}'), 1);
+    assert_eq!(char_width('\u{2FFFD}'), 2);
+
+    assert_eq!(visible_width(""), 0);
+    assert_eq!(visible_width("hello"), 5);
+    assert_eq!(visible_width("e\u{301}"), 1);
+    assert_eq!(visible_width("\u{1B}[1;31m漢字\u{1B}[0m!"), 5);
+    assert_eq!(visible_width("\u{1B}]8;;http://é.com\u{1B}\\link\u{1B}]8;;\u{7}"), 4);
+
+    let text = format!("{}漢字{}", BOLD, RESET);
+    assert_eq!(format!("{:<6}|", Padded(&text)), format!("{}  |", text));
+    assert_eq!(format!("{:>6}|", Padded(&text)), format!("  {}|", text));
+    assert_eq!(format!("{:-^7}|", Padded(&text)), format!("-{}--|", text));
+    assert_eq!(format!("{:2}|", Padded(&text)), format!("{}|", text));
+    assert_eq!(format!("{}|", Padded(&text)), format!("{}|", text));
+}
+
+/// Check colour level detection from the environment
+#[test]
+fn colour_level_env() {
+    use std::collections::HashMap;
+    use term_ctrl::colour::ColourLevel;
+
+    fn level(is_tty: bool, vars: &[(&str, &str)]) -> ColourLevel {
+        let env: HashMap<&str, &str> = vars.iter().cloned().collect();
+        support::colour_level(is_tty, |name| env.get(name).cloned())
+    }
+
+    assert_eq!(level(true, &[]), ColourLevel::Ansi16);
+    assert_eq!(level(false, &[]), ColourLevel::None);
+    assert_eq!(level(true, &[("TERM", "xterm")]), ColourLevel::Ansi16);
+    assert_eq!(level(true, &[("TERM", "xterm-256color")]), ColourLevel::Ansi256);
+    assert_eq!(level(true, &[("TERM", "xterm-direct")]), ColourLevel::TrueColour);
+    assert_eq!(level(true, &[("TERM", "xterm"), ("COLORTERM", "24bit")]), ColourLevel::TrueColour);
+    assert_eq!(level(true, &[("COLORTERM", "truecolor")]), ColourLevel::TrueColour);
+    assert_eq!(level(true, &[("COLORTERM", "yes")]), ColourLevel::Ansi16);
+    assert_eq!(level(true, &[("TERM", "dumb"), ("COLORTERM", "truecolor")]), ColourLevel::None);
+    assert_eq!(level(true, &[("NO_COLOR", "1"), ("TERM", "xterm-256color")]), ColourLevel::None);
+    assert_eq!(level(true, &[("NO_COLOR", "")]), ColourLevel::Ansi16);
+    assert_eq!(level(true, &[("CLICOLOR", "0")]), ColourLevel::None);
+    assert_eq!(level(true, &[("CLICOLOR", "1")]), ColourLevel::Ansi16);
+    // Forcing overrides everything else
+    assert_eq!(level(false, &[("CLICOLOR_FORCE", "1")]), ColourLevel::Ansi16);
+    assert_eq!(level(false, &[("CLICOLOR_FORCE", "1"), ("TERM", "screen-256color")]),
+        ColourLevel::Ansi256);
+    assert_eq!(level(true, &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1"), ("TERM", "dumb")]),
+        ColourLevel::Ansi16);
+    assert_eq!(level(false, &[("CLICOLOR_FORCE", "0")]), ColourLevel::None);
+}
+
+/// Check parsing and resolving of colour choices
+#[test]
+fn colour_choice() {
+    use term_ctrl::colour::ColourLevel;
+    use term_ctrl::support::ColourChoice;
+
+    assert_eq!("always".parse(), Ok(ColourChoice::Always));
+    assert_eq!("ANSI".parse(), Ok(ColourChoice::Always));
+    assert_eq!("auto".parse(), Ok(ColourChoice::Auto));
+    assert_eq!("if-tty".parse(), Ok(ColourChoice::Auto));
+    assert_eq!("Never".parse(), Ok(ColourChoice::Never));
+    assert!("sometimes".parse::<ColourChoice>().is_err());
+    assert!("".parse::<ColourChoice>().is_err());
+
+    assert_eq!(ColourChoice::from_arg("--color"), Some(Ok(ColourChoice::Always)));
+    assert_eq!(ColourChoice::from_arg("--colour=auto"), Some(Ok(ColourChoice::Auto)));
+    assert_eq!(ColourChoice::from_arg("--color=no"), Some(Ok(ColourChoice::Never)));
+    assert!(ColourChoice::from_arg("--color=").unwrap().is_err());
+    assert_eq!(ColourChoice::from_arg("--colors"), None);
+    assert_eq!(ColourChoice::from_arg("always"), None);
+
+    let env = [("TERM", "xterm-256color"), ("NO_COLOR", "1")];
+    let lookup = |name: &str| env.iter().find(|e| e.0 == name).map(|e| e.1);
+    assert_eq!(ColourChoice::Always.resolve(false, lookup), ColourLevel::Ansi256);
+    assert_eq!(ColourChoice::Auto.resolve(true, lookup), ColourLevel::None);
+    assert_eq!(ColourChoice::Auto.resolve(true, |_: &str| None::<&str>), ColourLevel::Ansi16);
+    assert_eq!(ColourChoice::Never.resolve(true, |_: &str| None::<&str>), ColourLevel::None);
+    assert_eq!(ColourChoice::Always.resolve(false, |_: &str| Some("dumb")), ColourLevel::Ansi16);
+}
+
+/// Check the guard restores state
+#[cfg(feature = "std")]
+#[test]
+fn guard() {
+    use std::io::Write;
+    use term_ctrl::guard::Guard;
+    use term_ctrl::modes::Mode;
+    use predefined::{RESET, modes};
+
+    // Nothing changed, nothing to restore
+    let mut out = Guard::new(Vec::new());
+    write!(out, "plain").unwrap();
+    assert_eq!(out.into_inner(), b"plain");
+
+    // Modes restored, with default-set modes set again
+    let mut out = Guard::new(Vec::new());
+    out.set_mode(Mode::AltScreen).unwrap();
+    out.reset_mode(Mode::CursorVisible).unwrap();
+    out.set_mode(Mode::BracketedPaste).unwrap();
+    write!(out, "{}x", predefined::effects::BOLD).unwrap();
+    let expected = [
+        modes::ALT_SCREEN, modes::reset::CURSOR_VISIBLE, modes::BRACKETED_PASTE,
+        predefined::effects::BOLD, "x",
+        RESET, modes::reset::BRACKETED_PASTE, modes::CURSOR_VISIBLE, modes::reset::ALT_SCREEN,
+    ].concat();
+    assert_eq!(String::from_utf8(out.into_inner()).unwrap(), expected);
+
+    // Changes undone through the guard need no restoring
+    let mut out = Guard::new(Vec::new());
+    out.reset_mode(Mode::CursorVisible).unwrap();
+    out.set_mode(Mode::CursorVisible).unwrap();
+    out.restore().unwrap();
+    assert_eq!(String::from_utf8(out.get_mut().clone()).unwrap(),
+        [modes::reset::CURSOR_VISIBLE, modes::CURSOR_VISIBLE].concat());
+
+    // Restores on drop, including when unwinding
+    let mut buf = Vec::new();
+    {
+        let mut out = Guard::new(&mut buf);
+        out.set_mode(Mode::MouseSgr).unwrap();
+    }
+    assert_eq!(buf, [modes::MOUSE_SGR, modes::reset::MOUSE_SGR].concat().as_bytes());
+    let buf = std::sync::Mutex::new(Vec::new());
+    let _ = std::panic::catch_unwind(|| {
+        let mut guard = buf.lock().unwrap();
+        let mut out = Guard::new(&mut *guard);
+        out.set_mode(Mode::SyncOutput).unwrap();
+        panic!("oops");
+    });
+    let buf = buf.into_inner().unwrap_or_else(|e| e.into_inner());
+    assert_eq!(buf, [modes::SYNC_OUTPUT, modes::reset::SYNC_OUTPUT].concat().as_bytes());
+}
+
+/// Check the panic hook restores state for guards of stdout and stderr
+///
+/// The hook is process-wide and writes to the real stdout and stderr, so this runs
+/// [`guard_panic_hook_child()`] in a separate process.
+#[cfg(feature = "std")]
+#[test]
+fn guard_panic_hook() {
+    use predefined::{RESET, modes};
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(&["guard_panic_hook_child", "--exact", "--nocapture", "--test-threads=1"])
+        .env("TERM_CTRL_GUARD_CHILD", "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    // Other guards, both of stdout and not, did not wipe out the record of the first
+    let expected = [modes::ALT_SCREEN, modes::BRACKETED_PASTE, modes::reset::BRACKETED_PASTE,
+        predefined::effects::BOLD, "x", RESET, modes::reset::ALT_SCREEN].concat();
+    assert!(stdout.contains(&expected), "{:?}", stdout);
+    let expected = [modes::reset::CURSOR_VISIBLE, modes::CURSOR_VISIBLE].concat();
+    assert!(stderr.contains(&expected), "{:?}", stderr);
+    assert!(!stdout.contains(modes::CURSOR_VISIBLE));
+    assert!(!stdout.contains(modes::MOUSE_SGR));
+}
+
+/// Makes changes through various guards and then panics, for [`guard_panic_hook()`]
+#[cfg(feature = "std")]
+#[test]
+fn guard_panic_hook_child() {
+    use std::io::Write;
+    use term_ctrl::guard::{self, Guard};
+    use term_ctrl::modes::Mode;
+
+    if std::env::var_os("TERM_CTRL_GUARD_CHILD").is_none() {
+        return;
+    }
+    guard::install_panic_hook();
+    let mut out = Guard::stdout();
+    out.set_mode(Mode::AltScreen).unwrap();
+    let mut err = Guard::stderr();
+    err.reset_mode(Mode::CursorVisible).unwrap();
+    {
+        let mut other = Guard::new(Vec::new());
+        other.set_mode(Mode::MouseSgr).unwrap();
+        let mut out = Guard::stdout();
+        out.set_mode(Mode::BracketedPaste).unwrap();
+    }
+    write!(out, "{}x", predefined::effects::BOLD).unwrap();
+    out.flush().unwrap();
+    // The guards are leaked, so only the hook restores anything
+    std::mem::forget(out);
+    std::mem::forget(err);
+    panic!("oops");
+}
+
 #[cfg(not(windows))]
 mod platform {
     use super::*;
+    #[cfg(feature = "std")]
+    use std::os::unix::io::RawFd;
+
+    /// Opens a pseudo-terminal, returning the master and slave file descriptors
+    #[cfg(feature = "std")]
+    pub fn open_pty() -> (RawFd, RawFd) {
+        unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(master >= 0);
+            assert_eq!(libc::grantpt(master), 0);
+            assert_eq!(libc::unlockpt(master), 0);
+            let name = libc::ptsname(master);
+            assert!(!name.is_null());
+            let slave = libc::open(name, libc::O_RDWR | libc::O_NOCTTY);
+            assert!(slave >= 0);
+            (master, slave)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn terminal_size() {
+        use term_ctrl::support::{terminal_size, TerminalSize};
+
+        let (master, slave) = open_pty();
+        let ws = libc::winsize { ws_row: 24, ws_col: 80, ws_xpixel: 640, ws_ypixel: 384 };
+        assert_eq!(unsafe { libc::ioctl(master, libc::TIOCSWINSZ, &ws) }, 0);
+        assert_eq!(terminal_size(slave),
+            Some(TerminalSize { cols: 80, rows: 24, width: 640, height: 384 }));
+
+        // A zero size is taken as unknown
+        let ws = libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+        assert_eq!(unsafe { libc::ioctl(master, libc::TIOCSWINSZ, &ws) }, 0);
+        assert_eq!(terminal_size(slave), None);
+
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+        // Not a terminal
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        assert_eq!(terminal_size(fds[0]), None);
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn raw_mode() {
+        use term_ctrl::support::RawMode;
+
+        let get = |fd| unsafe {
+            let mut termios = std::mem::zeroed::<libc::termios>();
+            assert_eq!(libc::tcgetattr(fd, &mut termios), 0);
+            termios
+        };
+        let (master, slave) = open_pty();
+        let original = get(slave);
+        assert_ne!(original.c_lflag & libc::ICANON, 0);
+        assert_ne!(original.c_lflag & libc::ECHO, 0);
+
+        {
+            let _raw = RawMode::raw(slave).unwrap();
+            let termios = get(slave);
+            assert_eq!(termios.c_lflag & (libc::ICANON | libc::ECHO | libc::ISIG), 0);
+            assert_eq!(termios.c_oflag & libc::OPOST, 0);
+            assert_eq!(termios.c_cc[libc::VMIN], 1);
+        }
+        let restored = get(slave);
+        assert_eq!(restored.c_lflag, original.c_lflag);
+        assert_eq!(restored.c_iflag, original.c_iflag);
+        assert_eq!(restored.c_oflag, original.c_oflag);
+
+        let cbreak = RawMode::cbreak(slave).unwrap();
+        let termios = get(slave);
+        assert_eq!(termios.c_lflag & (libc::ICANON | libc::ECHO), 0);
+        // Signals and output processing are left alone
+        assert_ne!(termios.c_lflag & libc::ISIG, 0);
+        assert_eq!(termios.c_oflag, original.c_oflag);
+        cbreak.restore().unwrap();
+        assert_eq!(get(slave).c_lflag, original.c_lflag);
+
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+        // Not a terminal
+        assert!(RawMode::raw(-1).is_err());
+    }
+
+    /// Reads from a file descriptor, giving the number of bytes read
+    #[cfg(feature = "std")]
+    fn read_fd(fd: RawFd, buf: &mut [u8]) -> usize {
+        let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        assert!(n > 0);
+        n as usize
+    }
+
+    /// Writes all of `data` to a file descriptor
+    #[cfg(feature = "std")]
+    fn write_fd(fd: RawFd, data: &[u8]) {
+        let n = unsafe { libc::write(fd, data.as_ptr() as *const libc::c_void, data.len()) };
+        assert_eq!(n, data.len() as isize);
+    }
+
+    /// Plays the part of a terminal on a pty master, giving a reply once queries are received
+    #[cfg(feature = "std")]
+    fn fake_terminal(master: RawFd, reply: &'static [u8]) -> std::thread::JoinHandle<()> {
+        std::thread::spawn(move || {
+            let mut received = Vec::new();
+            while !received.ends_with(b"\x1B[c") {
+                let mut buf = [0u8; 64];
+                let n = read_fd(master, &mut buf);
+                received.extend_from_slice(&buf[..n]);
+            }
+            write_fd(master, reply);
+        })
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn round_trip() {
+        use std::time::Duration;
+        use term_ctrl::query::{DA1, XTVERSION};
+        use term_ctrl::support::round_trip;
+
+        let timeout = Duration::from_secs(5);
+        let (master, slave) = open_pty();
+
+        // Supported
+        let terminal = fake_terminal(master, b"\x1BP>|Fake(1)\x1B\\\x1B[?62;22c");
+        assert_eq!(round_trip(slave, XTVERSION, timeout).unwrap(),
+            (Some(b"\x1BP>|Fake(1)\x1B\\".to_vec()), vec![]));
+        terminal.join().unwrap();
+
+        // Not supported
+        let terminal = fake_terminal(master, b"\x1B[?62;22c");
+        assert_eq!(round_trip(slave, XTVERSION, timeout).unwrap(), (Some(vec![]), vec![]));
+        terminal.join().unwrap();
+
+        // Keys pressed before the replies are kept apart
+        let terminal = fake_terminal(master, b"x\x1B[A\x1BP>|Fake(1)\x1B\\\x1B[?62;22c");
+        assert_eq!(round_trip(slave, XTVERSION, timeout).unwrap(),
+            (Some(b"\x1BP>|Fake(1)\x1B\\".to_vec()), b"x\x1B[A".to_vec()));
+        terminal.join().unwrap();
+        let terminal = fake_terminal(master, b"xyz\x1B[?62;22c");
+        assert_eq!(round_trip(slave, XTVERSION, timeout).unwrap(),
+            (Some(vec![]), b"xyz".to_vec()));
+        terminal.join().unwrap();
+
+        // DA1 itself
+        let terminal = fake_terminal(master, b"x\x1B[?62;22c");
+        assert_eq!(round_trip(slave, DA1, timeout).unwrap(),
+            (Some(b"\x1B[?62;22c".to_vec()), b"x".to_vec()));
+        terminal.join().unwrap();
+
+        // No reply at all
+        let terminal = fake_terminal(master, b"x");
+        assert_eq!(round_trip(slave, XTVERSION, Duration::from_millis(50)).unwrap(),
+            (None, b"x".to_vec()));
+        terminal.join().unwrap();
+
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn background_from_terminal() {
+        use std::time::Duration;
+        use term_ctrl::background::Background;
+
+        let timeout = Duration::from_secs(5);
+        let (master, slave) = open_pty();
+
+        let terminal = fake_terminal(master, b"x\x1B]11;rgb:ffff/ffff/ffff\x1B\\\x1B[?62;22c");
+        assert_eq!(Background::from_terminal(slave, timeout).unwrap(),
+            (Background::Light, b"x".to_vec()));
+        terminal.join().unwrap();
+
+        // Not supported
+        let terminal = fake_terminal(master, b"\x1B[?62;22c");
+        assert_eq!(Background::from_terminal(slave, timeout).unwrap(),
+            (Background::Unknown, vec![]));
+        terminal.join().unwrap();
+
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cursor_position() {
+        use std::time::Duration;
+        use term_ctrl::cursor::Position;
+        use term_ctrl::support::cursor_position;
+
+        let (master, slave) = open_pty();
+        let (consumed, wait) = std::sync::mpsc::channel::<()>();
+        let terminal = std::thread::spawn(move || {
+            let mut buf = [0u8; 16];
+            let n = read_fd(master, &mut buf);
+            assert_eq!(&buf[..n], b"\x1B[6n");
+            // Keys pressed before the report
+            write_fd(master, b"x\x1B[A\x1B[7;12R");
+            // And one pressed after it has been read
+            wait.recv().unwrap();
+            write_fd(master, b"y");
+        });
+        let (position, other) = cursor_position(slave, Duration::from_secs(5)).unwrap();
+        assert_eq!(position, Some(Position::new(7, 12)));
+        assert_eq!(other, b"x\x1B[A");
+        consumed.send(()).unwrap();
+        terminal.join().unwrap();
+
+        // No reply, just the key pressed since
+        let (position, other) = cursor_position(slave, Duration::from_millis(50)).unwrap();
+        assert_eq!(position, None);
+        assert_eq!(other, b"y");
+
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn resize_watch() {
+        assert!(support::watch_resize());
+        assert!(!support::resized());
+        assert_eq!(unsafe { libc::raise(libc::SIGWINCH) }, 0);
+        assert!(support::resized());
+        assert!(!support::resized());
+    }
 
     #[test]
     fn fmt_supported() {
         // Assuming test env has them connected to tty
-        assert_eq!(true, support::fmt_supported_stdout());
-        assert_eq!(true, support::fmt_supported_stderr());
+        assert!(support::fmt_supported_stdout());
+        assert!(support::fmt_supported_stderr());
     }
 
     #[test]
     fn fmt_supported_withpref() {
         // Assuming test env has them connected to tty
-        assert_eq!(true, support::use_fmt_stdout(true));
-        assert_eq!(true, support::use_fmt_stderr(true));
-        assert_eq!(false, support::use_fmt_stdout(false));
-        assert_eq!(false, support::use_fmt_stderr(false));
+        assert!(support::use_fmt_stdout(true));
+        assert!(support::use_fmt_stderr(true));
+        assert!(!support::use_fmt_stdout(false));
+        assert!(!support::use_fmt_stderr(false));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn colour_choice_resolve() {
+        use term_ctrl::colour::ColourLevel;
+        use term_ctrl::support::ColourChoice;
+
+        assert_eq!(ColourLevel::None, ColourChoice::Never.resolve_stdout());
+        assert_eq!(ColourLevel::None, ColourChoice::Never.resolve_stderr());
+        assert!(ColourChoice::Always.resolve_stdout() >= ColourLevel::Ansi16);
+        assert!(ColourChoice::Always.resolve_stderr() >= ColourLevel::Ansi16);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn writer_std_streams() {
+        // Assuming test env has them connected to tty
+        assert!(!writer::Writer::stdout(true).is_stripping());
+        assert!(!writer::Writer::stderr(true).is_stripping());
+        assert!(writer::Writer::stdout(false).is_stripping());
     }
 }
@@ -0,0 +1,123 @@
+#!/usr/bin/env python3
+#
+# Generates `src/tables.rs` from the Unicode Character Database bundled with Python:
+#
+#     python3 tools/gen_width_tables.py > src/tables.rs
+#
+# The database version depends upon the version of Python used (Python 3.11 and 3.12 bundle
+# Unicode 14.0.0 and 15.0.0 respectively, for instance). So that the tables do not change
+# depending upon who last ran this, it must be run with a Python bundling the version given by
+# `UNICODE_VERSION` below; update that deliberately when moving to a newer version of Unicode.
+
+import sys
+import unicodedata
+
+# The Unicode version the tables are generated from
+UNICODE_VERSION = "14.0.0"
+
+# Maximum line length of the generated code
+MAX_LINE_LENGTH = 100
+
+# The range of surrogate code points, which are not valid `char`s
+SURROGATES = range(0xD800, 0xE000)
+
+# Ranges of unassigned code points taken to be wide (as per the defaults of `EastAsianWidth.txt`)
+RESERVED_WIDE = [
+    range(0x3400, 0x4DC0),    # CJK Unified Ideographs Extension A
+    range(0x4E00, 0xA000),    # CJK Unified Ideographs
+    range(0xF900, 0xFB00),    # CJK Compatibility Ideographs
+    range(0x20000, 0x2FFFE),  # Supplementary Ideographic Plane
+    range(0x30000, 0x3FFFE),  # Tertiary Ideographic Plane
+]
+
+
+def ranges(predicate):
+    """Gets the inclusive ranges of code points for which `predicate` holds"""
+    found = []
+    start = None
+    for code_point in range(sys.maxunicode + 1):
+        if predicate(code_point):
+            if start is None:
+                start = code_point
+        elif start is not None:
+            found.append((start, code_point - 1))
+            start = None
+    if start is not None:
+        found.append((start, sys.maxunicode))
+    return found
+
+
+def is_zero_width(code_point):
+    """Whether a code point takes up no width
+
+    This covers combining marks, format chars (except the soft hyphen, which is typically shown),
+    Hangul medial and final jamo (which combine with initial jamo), and the zero width space.
+    """
+    if code_point == 0xAD:
+        return False
+    if unicodedata.category(chr(code_point)) in ("Mn", "Me", "Cf"):
+        return True
+    return 0x1160 <= code_point <= 0x11FF or code_point == 0x200B
+
+
+def is_wide(code_point):
+    """Whether a code point takes up two columns (East Asian Wide or Fullwidth)
+
+    Python gives Fullwidth for unassigned code points, whereas `EastAsianWidth.txt` takes them to
+    be Neutral, other than those within the blocks and planes reserved for CJK ideographs, which
+    are Wide. The latter is followed here.
+    """
+    if is_zero_width(code_point):
+        return False
+    if unicodedata.category(chr(code_point)) == "Cn":
+        return any(code_point in reserved for reserved in RESERVED_WIDE)
+    return unicodedata.east_asian_width(chr(code_point)) in ("W", "F")
+
+
+def format_table(name, doc, table):
+    """Formats a table of ranges as a Rust static, packing as many ranges per line as fit"""
+    lines = [f"/// {doc}", f"pub(crate) static {name}: &[(char, char)] = &["]
+    line = "   "
+    for start, end in table:
+        if start in SURROGATES or end in SURROGATES:
+            continue
+        item = f"('\\u{{{start:X}}}', '\\u{{{end:X}}}'),"
+        if len(line) + 1 + len(item) > MAX_LINE_LENGTH:
+            lines.append(line)
+            line = "   "
+        line += " " + item
+    lines.append(line)
+    lines.append("];")
+    return "\n".join(lines)
+
+
+HEADER = f"""\
+// Copyright 2026 Lyndon Brown
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Character width tables
+//!
+//! Generated by `tools/gen_width_tables.py` from the Unicode Character Database, version
+//! {UNICODE_VERSION}. Do not edit by hand."""
+
+
+def main():
+    if unicodedata.unidata_version != UNICODE_VERSION:
+        sys.exit(f"error: Unicode {UNICODE_VERSION} is required, but this Python bundles "
+                 f"{unicodedata.unidata_version}; run with a matching version of Python")
+
+    zero_width = format_table(
+        "ZERO_WIDTH",
+        "Zero width chars (combining marks, format chars, and Hangul medial and final jamo)",
+        ranges(is_zero_width))
+    wide = format_table("WIDE", "Wide chars (East Asian Wide and Fullwidth)", ranges(is_wide))
+    print(f"{HEADER}\n\n{zero_width}\n\n{wide}")
+
+
+if __name__ == "__main__":
+    main()